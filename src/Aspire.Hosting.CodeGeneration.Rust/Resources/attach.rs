@@ -0,0 +1,173 @@
+//! Demultiplexed container log/attach streaming.
+//!
+//! Mirrors the TTY multiplexing container engines use on an `attach`-style
+//! connection: the host pushes each chunk of the container's combined
+//! stdin/stdout/stderr stream, base64-encoded, over the ordinary callback
+//! registry (see `base::subscribe_callback`); `FrameDemuxer` reassembles the
+//! raw bytes into individually-tagged `LogFrame`s.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::transport::{self, AspireClient};
+
+/// Which of a container's standard streams a `LogFrame` came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    Stdin,
+    Stdout,
+    Stderr,
+}
+
+/// One demultiplexed chunk from a container's attach stream.
+#[derive(Debug, Clone)]
+pub struct LogFrame {
+    pub stream: StreamKind,
+    pub bytes: Vec<u8>,
+}
+
+/// Incrementally parses the multiplexed stream framing: each frame is an
+/// 8-byte header (byte 0 = stream type 0/1/2, bytes 1-3 reserved, bytes 4-7
+/// a big-endian `u32` payload length) followed by exactly that many payload
+/// bytes. `push` appends however many raw bytes just arrived — which may
+/// split a frame's header or payload across two pushes — and returns every
+/// frame that's now complete, leaving a partial trailing frame buffered for
+/// the next push.
+#[derive(Default)]
+struct FrameDemuxer {
+    buf: Vec<u8>,
+}
+
+impl FrameDemuxer {
+    fn push(&mut self, chunk: &[u8]) -> Vec<LogFrame> {
+        self.buf.extend_from_slice(chunk);
+        let mut frames = Vec::new();
+        loop {
+            if self.buf.len() < 8 {
+                break;
+            }
+            let len = u32::from_be_bytes([self.buf[4], self.buf[5], self.buf[6], self.buf[7]]) as usize;
+            if self.buf.len() < 8 + len {
+                break;
+            }
+            let stream = match self.buf[0] {
+                0 => StreamKind::Stdin,
+                1 => StreamKind::Stdout,
+                _ => StreamKind::Stderr,
+            };
+            let bytes = self.buf[8..8 + len].to_vec();
+            self.buf.drain(..8 + len);
+            frames.push(LogFrame { stream, bytes });
+        }
+        frames
+    }
+}
+
+/// An iterator over demultiplexed `LogFrame`s from a container's attach
+/// stream. Each host push carries one base64-encoded raw chunk, which may
+/// contain zero, one, or several complete frames (or split one across two
+/// pushes); `next()` drains already-decoded frames before blocking on the
+/// next push. Dropping the stream unsubscribes, the same as `EventStream`.
+pub struct LogFrameStream {
+    receiver: std::sync::mpsc::Receiver<Value>,
+    subscription_id: String,
+    unsubscribe_capability: String,
+    client: Arc<AspireClient>,
+    demuxer: FrameDemuxer,
+    pending: VecDeque<LogFrame>,
+}
+
+impl LogFrameStream {
+    pub(crate) fn new(
+        receiver: std::sync::mpsc::Receiver<Value>,
+        subscription_id: impl Into<String>,
+        unsubscribe_capability: impl Into<String>,
+        client: Arc<AspireClient>,
+    ) -> Self {
+        Self {
+            receiver,
+            subscription_id: subscription_id.into(),
+            unsubscribe_capability: unsubscribe_capability.into(),
+            client,
+            demuxer: FrameDemuxer::default(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Blocks until the next frame is available, or the container's attach
+    /// stream ends (the host closes the subscription and the underlying
+    /// channel disconnects).
+    pub fn recv(&mut self) -> Option<LogFrame> {
+        self.next()
+    }
+}
+
+impl Iterator for LogFrameStream {
+    type Item = LogFrame;
+
+    fn next(&mut self) -> Option<LogFrame> {
+        loop {
+            if let Some(frame) = self.pending.pop_front() {
+                return Some(frame);
+            }
+            let value = self.receiver.recv().ok()?;
+            let Some(encoded) = value.as_str() else { continue };
+            let Some(bytes) = base64_decode(encoded) else { continue };
+            self.pending.extend(self.demuxer.push(&bytes));
+        }
+    }
+}
+
+impl Drop for LogFrameStream {
+    fn drop(&mut self) {
+        transport::unregister_subscription(&self.subscription_id);
+        let mut args = std::collections::HashMap::new();
+        args.insert("callback".to_string(), Value::String(self.subscription_id.clone()));
+        let _ = self.client.invoke_capability(&self.unsubscribe_capability, args);
+    }
+}
+
+/// Opens a demultiplexed attach stream for `resource`, invoking
+/// `Aspire.Hosting/attachContainer` and decoding each pushed chunk via
+/// `LogFrameStream`. `follow` mirrors `ContainerResource::logs`: `false`
+/// yields the currently buffered tail and then ends; `true` stays open
+/// until dropped or the container exits.
+pub fn attach_container(
+    resource: Value,
+    follow: bool,
+    client: Arc<AspireClient>,
+) -> Result<LogFrameStream, crate::error::AspireError> {
+    let (subscription_id, receiver) = transport::register_subscription();
+    let mut args = std::collections::HashMap::new();
+    args.insert("resource".to_string(), resource);
+    args.insert("follow".to_string(), Value::Bool(follow));
+    args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+    if let Err(e) = client.invoke_capability("Aspire.Hosting/attachContainer", args) {
+        transport::unregister_subscription(&subscription_id);
+        return Err(e);
+    }
+    Ok(LogFrameStream::new(receiver, subscription_id, "Aspire.Hosting/unattachContainer", client))
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decodes a standard (with `=` padding) base64 string. Returns `None` on
+/// malformed input rather than erroring the whole stream over one bad push.
+fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=');
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buf: u32 = 0;
+    let mut bits = 0;
+    for c in input.bytes() {
+        let value = BASE64_ALPHABET.iter().position(|&b| b == c)? as u32;
+        buf = (buf << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buf >> bits) as u8);
+        }
+    }
+    Some(out)
+}
@@ -1,12 +1,14 @@
 //! Base types for Aspire Rust SDK.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use serde::de::DeserializeOwned;
 use serde::{Serialize, Deserialize};
 use serde_json::{json, Value};
 
-use crate::transport::{AspireClient, Handle};
+use crate::error::AspireError;
+use crate::transport::{self, AspireClient, Handle};
 
 /// Base type for all handle wrappers.
 pub struct HandleWrapperBase {
@@ -84,19 +86,23 @@ pub struct AspireList<T> {
     context_handle: Handle,
     client: Arc<AspireClient>,
     getter_capability_id: Option<String>,
-    resolved_handle: std::cell::OnceCell<Handle>,
+    /// Lazily resolved via `getter_capability_id`, same as a `OnceCell`
+    /// would be, but behind a `Mutex` rather than `std::cell::OnceCell` so
+    /// `invalidate_handle` can clear it through `&self` — a server-side
+    /// reconnect (see `AspireClient::on_reconnect`) can reissue this
+    /// object's handle, and there's no way to get a `&mut self` to a value
+    /// an application is holding onto across that event.
+    resolved_handle: Mutex<Option<Handle>>,
     _marker: std::marker::PhantomData<T>,
 }
 
 impl<T> AspireList<T> {
     pub fn new(handle: Handle, client: Arc<AspireClient>) -> Self {
-        let resolved = std::cell::OnceCell::new();
-        let _ = resolved.set(handle.clone());
         Self {
-            context_handle: handle,
+            context_handle: handle.clone(),
             client,
             getter_capability_id: None,
-            resolved_handle: resolved,
+            resolved_handle: Mutex::new(Some(handle)),
             _marker: std::marker::PhantomData,
         }
     }
@@ -106,30 +112,46 @@ impl<T> AspireList<T> {
             context_handle,
             client,
             getter_capability_id: Some(getter_capability_id.into()),
-            resolved_handle: std::cell::OnceCell::new(),
+            resolved_handle: Mutex::new(None),
             _marker: std::marker::PhantomData,
         }
     }
 
-    fn ensure_handle(&self) -> &Handle {
-        self.resolved_handle.get_or_init(|| {
-            if let Some(ref cap_id) = self.getter_capability_id {
-                let mut args = HashMap::new();
-                args.insert("context".to_string(), self.context_handle.to_json());
-                if let Ok(result) = self.client.invoke_capability(cap_id, args) {
-                    if let Ok(handle) = serde_json::from_value::<Handle>(result) {
-                        return handle;
-                    }
-                }
-            }
+    fn ensure_handle(&self) -> Handle {
+        let mut resolved = self.resolved_handle.lock().unwrap();
+        if let Some(handle) = resolved.as_ref() {
+            return handle.clone();
+        }
+        let handle = if let Some(ref cap_id) = self.getter_capability_id {
+            let mut args = HashMap::new();
+            args.insert("context".to_string(), self.context_handle.to_json());
+            self.client
+                .invoke_capability(cap_id, args)
+                .ok()
+                .and_then(|result| serde_json::from_value::<Handle>(result).ok())
+                .unwrap_or_else(|| self.context_handle.clone())
+        } else {
             self.context_handle.clone()
-        })
+        };
+        *resolved = Some(handle.clone());
+        handle
     }
 
-    pub fn handle(&self) -> &Handle {
+    pub fn handle(&self) -> Handle {
         self.ensure_handle()
     }
 
+    /// Clears this list's resolved handle so the next call re-runs
+    /// `getter_capability_id` instead of reusing one the AppHost may have
+    /// invalidated — call from an `AspireClient::on_reconnect` callback.
+    /// A no-op for a list constructed via `new` (no getter to re-resolve
+    /// through, so there's nothing to re-fetch).
+    pub fn invalidate_handle(&self) {
+        if self.getter_capability_id.is_some() {
+            *self.resolved_handle.lock().unwrap() = None;
+        }
+    }
+
     pub fn client(&self) -> &Arc<AspireClient> {
         &self.client
     }
@@ -140,20 +162,20 @@ pub struct AspireDict<K, V> {
     context_handle: Handle,
     client: Arc<AspireClient>,
     getter_capability_id: Option<String>,
-    resolved_handle: std::cell::OnceCell<Handle>,
+    /// See `AspireList::resolved_handle` for why this is a `Mutex` rather
+    /// than a `OnceCell`.
+    resolved_handle: Mutex<Option<Handle>>,
     _key_marker: std::marker::PhantomData<K>,
     _value_marker: std::marker::PhantomData<V>,
 }
 
 impl<K, V> AspireDict<K, V> {
     pub fn new(handle: Handle, client: Arc<AspireClient>) -> Self {
-        let resolved = std::cell::OnceCell::new();
-        let _ = resolved.set(handle.clone());
         Self {
-            context_handle: handle,
+            context_handle: handle.clone(),
             client,
             getter_capability_id: None,
-            resolved_handle: resolved,
+            resolved_handle: Mutex::new(Some(handle)),
             _key_marker: std::marker::PhantomData,
             _value_marker: std::marker::PhantomData,
         }
@@ -164,36 +186,593 @@ impl<K, V> AspireDict<K, V> {
             context_handle,
             client,
             getter_capability_id: Some(getter_capability_id.into()),
-            resolved_handle: std::cell::OnceCell::new(),
+            resolved_handle: Mutex::new(None),
             _key_marker: std::marker::PhantomData,
             _value_marker: std::marker::PhantomData,
         }
     }
 
-    fn ensure_handle(&self) -> &Handle {
-        self.resolved_handle.get_or_init(|| {
-            if let Some(ref cap_id) = self.getter_capability_id {
-                let mut args = HashMap::new();
-                args.insert("context".to_string(), self.context_handle.to_json());
-                if let Ok(result) = self.client.invoke_capability(cap_id, args) {
-                    if let Ok(handle) = serde_json::from_value::<Handle>(result) {
-                        return handle;
-                    }
-                }
-            }
+    fn ensure_handle(&self) -> Handle {
+        let mut resolved = self.resolved_handle.lock().unwrap();
+        if let Some(handle) = resolved.as_ref() {
+            return handle.clone();
+        }
+        let handle = if let Some(ref cap_id) = self.getter_capability_id {
+            let mut args = HashMap::new();
+            args.insert("context".to_string(), self.context_handle.to_json());
+            self.client
+                .invoke_capability(cap_id, args)
+                .ok()
+                .and_then(|result| serde_json::from_value::<Handle>(result).ok())
+                .unwrap_or_else(|| self.context_handle.clone())
+        } else {
             self.context_handle.clone()
-        })
+        };
+        *resolved = Some(handle.clone());
+        handle
     }
 
-    pub fn handle(&self) -> &Handle {
+    pub fn handle(&self) -> Handle {
         self.ensure_handle()
     }
 
+    /// Clears this dict's resolved handle — see `AspireList::invalidate_handle`.
+    pub fn invalidate_handle(&self) {
+        if self.getter_capability_id.is_some() {
+            *self.resolved_handle.lock().unwrap() = None;
+        }
+    }
+
     pub fn client(&self) -> &Arc<AspireClient> {
         &self.client
     }
 }
 
+/// A stream of host-pushed events backed by a `watch`-style capability subscription.
+///
+/// Created by resource wrappers that expose `subscribe_events`-like methods. Each
+/// item received over the wire is deserialized into `T` as it arrives. Dropping the
+/// stream unregisters the subscription and invokes the matching unsubscribe
+/// capability so the host stops sending.
+pub struct EventStream<T> {
+    receiver: std::sync::mpsc::Receiver<Value>,
+    subscription_id: String,
+    unsubscribe_capability: String,
+    client: Arc<AspireClient>,
+    stop: Option<Arc<std::sync::atomic::AtomicBool>>,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: DeserializeOwned> EventStream<T> {
+    pub fn new(
+        receiver: std::sync::mpsc::Receiver<Value>,
+        subscription_id: impl Into<String>,
+        unsubscribe_capability: impl Into<String>,
+        client: Arc<AspireClient>,
+    ) -> Self {
+        Self {
+            receiver,
+            subscription_id: subscription_id.into(),
+            unsubscribe_capability: unsubscribe_capability.into(),
+            client,
+            stop: None,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Same as `new`, but also stops a background polling loop feeding
+    /// `receiver` on teardown (see `Subscription::with_stop_flag`), for
+    /// streams backed by a blocking-query thread rather than a host-pushed
+    /// notification.
+    pub fn with_stop_flag(
+        receiver: std::sync::mpsc::Receiver<Value>,
+        subscription_id: impl Into<String>,
+        unsubscribe_capability: impl Into<String>,
+        client: Arc<AspireClient>,
+        stop: Arc<std::sync::atomic::AtomicBool>,
+    ) -> Self {
+        Self {
+            receiver,
+            subscription_id: subscription_id.into(),
+            unsubscribe_capability: unsubscribe_capability.into(),
+            client,
+            stop: Some(stop),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Blocks until the next event arrives, or the host closes the subscription.
+    pub fn recv(&self) -> Result<T, Box<dyn std::error::Error>> {
+        let value = self.receiver.recv()?;
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for EventStream<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver
+            .recv()
+            .ok()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+}
+
+impl<T> Drop for EventStream<T> {
+    fn drop(&mut self) {
+        if let Some(stop) = &self.stop {
+            stop.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+        transport::unregister_subscription(&self.subscription_id);
+        let mut args = HashMap::new();
+        args.insert("callback".to_string(), Value::String(self.subscription_id.clone()));
+        let _ = self.client.invoke_capability(&self.unsubscribe_capability, args);
+    }
+}
+
+impl AspireClient {
+    /// Subscribes to an arbitrary notification topic that isn't scoped to one
+    /// resource (e.g. AppHost-wide lifecycle events), via the same generic
+    /// `Aspire.Hosting/subscribe` capability the per-resource
+    /// `subscribe_topic` wrappers use. The subscription id is minted locally
+    /// and sent as `callback`; the host routes every subsequent notification
+    /// carrying that id into the returned `EventStream`; dropping it sends
+    /// `unsubscribe`.
+    pub fn subscribe(self: &Arc<Self>, topic: &str) -> Result<EventStream<Value>, AspireError> {
+        let (subscription_id, receiver) = transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("topic".to_string(), Value::String(topic.to_string()));
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.invoke_capability("Aspire.Hosting/subscribe", args) {
+            transport::unregister_subscription(&subscription_id);
+            return Err(e);
+        }
+        Ok(EventStream::new(receiver, subscription_id, "Aspire.Hosting/unsubscribe", self.clone()))
+    }
+}
+
+/// A handle to an active callback-based subscription (pub/sub notifications,
+/// blocking-query watches, etc).
+///
+/// Notifications are keyed by the subscription id so concurrent subscriptions
+/// never cross-deliver. Dropping the subscription (or calling `unsubscribe`
+/// explicitly) unregisters the callback and tears down any background polling
+/// loop, then invokes the matching unsubscribe capability so the host stops
+/// pushing.
+pub struct Subscription {
+    id: String,
+    unsubscribe_capability: String,
+    client: Arc<AspireClient>,
+    stop: Option<Arc<std::sync::atomic::AtomicBool>>,
+    active: std::sync::atomic::AtomicBool,
+}
+
+impl Subscription {
+    pub fn new(id: impl Into<String>, unsubscribe_capability: impl Into<String>, client: Arc<AspireClient>) -> Self {
+        Self {
+            id: id.into(),
+            unsubscribe_capability: unsubscribe_capability.into(),
+            client,
+            stop: None,
+            active: std::sync::atomic::AtomicBool::new(true),
+        }
+    }
+
+    /// Same as `new`, but also stops the given background polling loop on teardown.
+    pub fn with_stop_flag(
+        id: impl Into<String>,
+        unsubscribe_capability: impl Into<String>,
+        client: Arc<AspireClient>,
+        stop: Arc<std::sync::atomic::AtomicBool>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            unsubscribe_capability: unsubscribe_capability.into(),
+            client,
+            stop: Some(stop),
+            active: std::sync::atomic::AtomicBool::new(true),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Tears down the subscription. Safe to call more than once.
+    pub fn unsubscribe(&self) {
+        if !self.active.swap(false, std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+        if let Some(stop) = &self.stop {
+            stop.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+        transport::unregister_callback(&self.id);
+        let mut args = HashMap::new();
+        args.insert("callback".to_string(), Value::String(self.id.clone()));
+        let _ = self.client.invoke_capability(&self.unsubscribe_capability, args);
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.unsubscribe();
+    }
+}
+
+/// Watches a resource's state/health using Consul-style blocking queries.
+///
+/// Maintains a monotonically increasing index on a background thread: each
+/// call to `Aspire.Hosting/watchResourceState` sends the last index seen plus
+/// a max-wait duration, and the host holds the response open until the index
+/// advances or the wait elapses. `callback` is invoked with the fresh state on
+/// every response. If the host ever returns an index *smaller* than the one
+/// sent (the resource was recreated), polling resets to index 0 instead of
+/// blocking forever. Drop the returned `Subscription` (or call `unsubscribe`)
+/// to stop the loop.
+pub fn watch_resource_state<F>(handle: Handle, client: Arc<AspireClient>, callback: F) -> Subscription
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    let callback = Arc::new(callback);
+    let registry_callback = callback.clone();
+    let callback_id = transport::register_callback(move |args| registry_callback(args));
+    let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let loop_stop = stop.clone();
+    let loop_client = client.clone();
+    let loop_callback_id = callback_id.clone();
+    let loop_callback = callback;
+    std::thread::spawn(move || {
+        let mut index: u64 = 0;
+        while !loop_stop.load(std::sync::atomic::Ordering::SeqCst) {
+            let mut args = HashMap::new();
+            args.insert("resource".to_string(), handle.to_json());
+            args.insert("index".to_string(), json!(index));
+            args.insert("wait".to_string(), json!("30s"));
+            args.insert("callback".to_string(), Value::String(loop_callback_id.clone()));
+            match loop_client.invoke_capability("Aspire.Hosting/watchResourceState", args) {
+                Ok(result) => {
+                    let new_index = result.get("index").and_then(|v| v.as_u64()).unwrap_or(index);
+                    let payload = result.get("payload").cloned().unwrap_or(Value::Null);
+                    loop_callback(vec![payload]);
+                    index = if new_index < index { 0 } else { new_index };
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    Subscription::with_stop_flag(callback_id, "Aspire.Hosting/unwatchResourceState", client, stop)
+}
+
+/// Shared Consul-style blocking-query loop behind `watch_resource_status`/
+/// `watch_resource_endpoint`: keeps a monotonically increasing `last_index`
+/// (starting at 0) and on each call passes `{ index: last_index, wait: "30s"
+/// }` — never an unchanged index with a zero wait, so the host always has a
+/// chance to block rather than the client busy-spinning. `callback` fires
+/// with the response's `payload` only when the returned index actually
+/// differs from `last_index`; a response carrying the same index is a
+/// timeout with no change and is simply reissued. If the host ever returns
+/// an index *smaller* than the one sent (it was recreated/restarted out from
+/// under the watch), the local index resets to 0 before the next query
+/// rather than waiting on a counter that will never catch up.
+fn watch_resource_indexed<F>(
+    handle: Handle,
+    endpoint_name: Option<String>,
+    client: Arc<AspireClient>,
+    callback: F,
+) -> Subscription
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    let callback = Arc::new(callback);
+    let registry_callback = callback.clone();
+    let callback_id = transport::register_callback(move |args| registry_callback(args));
+    let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let loop_stop = stop.clone();
+    let loop_client = client.clone();
+    let loop_callback_id = callback_id.clone();
+    let loop_callback = callback;
+    std::thread::spawn(move || {
+        let mut index: u64 = 0;
+        while !loop_stop.load(std::sync::atomic::Ordering::SeqCst) {
+            let mut args = HashMap::new();
+            args.insert("resource".to_string(), handle.to_json());
+            args.insert("index".to_string(), json!(index));
+            args.insert("wait".to_string(), json!("30s"));
+            if let Some(ref name) = endpoint_name {
+                args.insert("endpoint".to_string(), json!(name));
+            }
+            args.insert("callback".to_string(), Value::String(loop_callback_id.clone()));
+            match loop_client.invoke_capability("Aspire.Hosting/watchResource", args) {
+                Ok(result) => {
+                    let new_index = result.get("index").and_then(|v| v.as_u64()).unwrap_or(index);
+                    if new_index != index {
+                        let payload = result.get("payload").cloned().unwrap_or(Value::Null);
+                        loop_callback(vec![payload]);
+                    }
+                    index = if new_index < index { 0 } else { new_index };
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    Subscription::with_stop_flag(callback_id, "Aspire.Hosting/unwatchResource", client, stop)
+}
+
+/// Watches a resource's overall status via a Consul-style blocking query
+/// against `Aspire.Hosting/watchResource`. See `watch_resource_indexed` for
+/// the index/wait invariants. Drop the returned `Subscription` to stop.
+pub fn watch_resource_status<F>(handle: Handle, client: Arc<AspireClient>, callback: F) -> Subscription
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    watch_resource_indexed(handle, None, client, callback)
+}
+
+/// Like `watch_resource_status`, but scoped to a single named endpoint
+/// rather than the resource as a whole, for callers who only care about one
+/// endpoint's value changing (e.g. a reassigned port after a restart).
+pub fn watch_resource_endpoint<F>(
+    handle: Handle,
+    endpoint_name: impl Into<String>,
+    client: Arc<AspireClient>,
+    callback: F,
+) -> Subscription
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    watch_resource_indexed(handle, Some(endpoint_name.into()), client, callback)
+}
+
+/// One `watch_resource_state_stream` transition: `name` identifies the
+/// resource (useful once a caller is merging streams from more than one),
+/// `state` is the new state the blocking query observed, and `index` is the
+/// host's index at that observation, monotonically increasing for as long as
+/// the resource isn't recreated out from under the watch (see
+/// `watch_resource_state`'s doc comment for the recreate/rewind case).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResourceStateChange {
+    pub name: String,
+    pub state: String,
+    pub index: u64,
+}
+
+/// Pull-based counterpart to `watch_resource_state`: instead of invoking a
+/// callback on a background thread, each state transition is pushed onto a
+/// subscription channel and surfaced as an `EventStream<ResourceStateChange>`
+/// — an `Iterator` callers can `for change in stream { ... }` over to react
+/// to a resource becoming Healthy/Running/Exited, without writing their own
+/// index-tracking loop around `watch_resource_state`/`wait_for`. Dropping the
+/// stream stops the background thread and unsubscribes, same as
+/// `watch_resource_state`.
+pub fn watch_resource_state_stream(name: String, handle: Handle, client: Arc<AspireClient>) -> EventStream<ResourceStateChange> {
+    let (subscription_id, receiver) = transport::register_subscription();
+    let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let loop_stop = stop.clone();
+    let loop_client = client.clone();
+    let loop_subscription_id = subscription_id.clone();
+    std::thread::spawn(move || {
+        let mut index: u64 = 0;
+        while !loop_stop.load(std::sync::atomic::Ordering::SeqCst) {
+            let mut args = HashMap::new();
+            args.insert("resource".to_string(), handle.to_json());
+            args.insert("index".to_string(), json!(index));
+            match loop_client.invoke_capability("Aspire.Hosting/watchResourceState", args) {
+                Ok(result) => {
+                    let new_index = result.get("index").and_then(|v| v.as_u64()).unwrap_or(index);
+                    if new_index <= index {
+                        index = if new_index < index { 0 } else { index };
+                        continue;
+                    }
+                    index = new_index;
+                    let state = result.get("state").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                    let change = json!({ "name": name, "state": state, "index": index });
+                    if !transport::push_subscription_event(&loop_subscription_id, change) {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    EventStream::with_stop_flag(receiver, subscription_id, "Aspire.Hosting/unwatchResourceState", client, stop)
+}
+
+/// Small random delay inserted between `wait_for_state` retries so many
+/// concurrent waiters on the same resource don't hammer the host in lockstep
+/// after an internal timeout with no change. Not cryptographically
+/// meaningful — just enough spread (0-50ms) to avoid a thundering herd.
+fn retry_jitter() -> std::time::Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    std::time::Duration::from_millis((nanos % 50) as u64)
+}
+
+/// Blocks until `handle`'s state satisfies `predicate`, or `max_wait`
+/// elapses, using a Consul-style blocking query keyed by a monotonically
+/// increasing `changeIndex` rather than tight polling.
+///
+/// Each call to `Aspire.Hosting/waitForState` sends the last-seen index
+/// (starting at 0) plus the remaining wait budget; the host holds the
+/// request open until the index advances past it or the timeout elapses,
+/// then returns the resource's current state and its fresh index.
+/// `predicate` is checked against every observed state, including the
+/// first, so a resource already satisfying it returns immediately. If the
+/// host ever returns an index *lower* than the one sent (the resource was
+/// recreated out from under the wait), the local index resets to 0 rather
+/// than waiting on a counter that will never catch up; a response carrying
+/// the *same* index (a timeout with no change) is reissued immediately,
+/// with a small jitter (see `retry_jitter`) so concurrent waiters don't
+/// retry in lockstep.
+pub fn wait_for_state<F>(
+    handle: Handle,
+    client: Arc<AspireClient>,
+    max_wait: std::time::Duration,
+    predicate: F,
+) -> Result<Value, AspireError>
+where
+    F: Fn(&Value) -> bool,
+{
+    let deadline = std::time::Instant::now() + max_wait;
+    let mut index: u64 = 0;
+    loop {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        if remaining.is_zero() {
+            return Err(AspireError::WaitTimeout { last_index: index });
+        }
+
+        let mut args = HashMap::new();
+        args.insert("resource".to_string(), handle.to_json());
+        args.insert("index".to_string(), json!(index));
+        args.insert("waitMs".to_string(), json!(remaining.as_millis() as u64));
+        let result = client.invoke_capability("Aspire.Hosting/waitForState", args)?;
+
+        let state = result.get("state").cloned().unwrap_or(Value::Null);
+        let new_index = result.get("changeIndex").and_then(|v| v.as_u64()).unwrap_or(index);
+
+        if predicate(&state) {
+            return Ok(state);
+        }
+
+        if new_index < index {
+            index = 0;
+        } else if new_index == index {
+            std::thread::sleep(retry_jitter());
+        } else {
+            index = new_index;
+        }
+    }
+}
+
+/// Generic streaming-callback registration, the pattern `subscribe_logs` and
+/// `subscribe_events` both hand-roll: register `callback` so the host can
+/// invoke it repeatedly (as opposed to `register_callback`'s usual one-shot
+/// request/response turn), call `subscribe_capability` with `args` plus the
+/// resulting callback id under `"callback"`, and hand back a `Subscription`
+/// that unregisters the callback and calls `unsubscribe_capability` on drop.
+/// If the subscribe call itself fails, the callback is unregistered before
+/// returning the error so a failed subscription never leaks a registry entry.
+pub fn subscribe_callback<F>(
+    client: Arc<AspireClient>,
+    subscribe_capability: &str,
+    unsubscribe_capability: impl Into<String>,
+    mut args: HashMap<String, Value>,
+    callback: F,
+) -> Result<Subscription, AspireError>
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    let callback_id = transport::register_callback(callback);
+    args.insert("callback".to_string(), Value::String(callback_id.clone()));
+    if let Err(e) = client.invoke_capability(subscribe_capability, args) {
+        transport::unregister_callback(&callback_id);
+        return Err(e);
+    }
+    Ok(Subscription::new(callback_id, unsubscribe_capability, client))
+}
+
+/// Subscribes to a resource's stdout/stderr lines via the callback registry,
+/// JSON-RPC-pub/sub style: the host pushes notifications carrying the
+/// subscription id plus a payload, keyed so concurrent subscriptions never
+/// cross-deliver. Dropping the returned `Subscription` auto-unsubscribes.
+pub fn subscribe_logs<F>(handle: Handle, client: Arc<AspireClient>, callback: F) -> Result<Subscription, AspireError>
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    let mut args = HashMap::new();
+    args.insert("resource".to_string(), handle.to_json());
+    subscribe_callback(client, "Aspire.Hosting/subscribeLogs", "Aspire.Hosting/unsubscribeLogs", args, callback)
+}
+
+/// Subscribes to a resource's lifecycle events via the callback registry. See
+/// `subscribe_logs` for the delivery semantics; use `EventStream`-returning
+/// wrapper methods instead when a pull-based channel is more convenient.
+pub fn subscribe_events<F>(handle: Handle, client: Arc<AspireClient>, callback: F) -> Result<Subscription, AspireError>
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    let mut args = HashMap::new();
+    args.insert("resource".to_string(), handle.to_json());
+    subscribe_callback(client, "Aspire.Hosting/subscribeResourceEvents", "Aspire.Hosting/unsubscribeResourceEvents", args, callback)
+}
+
+/// Subscribes to a resource's status using the same push-based callback
+/// channel as `subscribe_events`/`subscribe_logs`, for callers who want a
+/// fired-repeatedly `Fn(Vec<Value>) -> Value` rather than the pull-based
+/// `EventStream` `watch_resource_state_stream` returns.
+pub fn with_resource_status_stream<F>(handle: Handle, client: Arc<AspireClient>, callback: F) -> Result<Subscription, AspireError>
+where
+    F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+{
+    let mut args = HashMap::new();
+    args.insert("resource".to_string(), handle.to_json());
+    subscribe_callback(client, "Aspire.Hosting/subscribeResourceStatus", "Aspire.Hosting/unsubscribeResourceStatus", args, callback)
+}
+
+/// Deferred/batched mode for a `with_*` configurator chain on a single
+/// resource handle: `queue` records `(capability_id, args)` without
+/// dispatching, and a terminal `apply()` flushes every queued mutation as one
+/// ordered list the host replays against the handle, returning the final
+/// handle. Where `AspireClient::batch` collapses independent calls into one
+/// JSON-RPC array, `DeferredBuilder` targets the chained single-handle case
+/// specifically: `add_container(...).deferred().with_status(...).with_endpoints(...).apply()`
+/// costs one round trip no matter how many `with_*` calls are queued, while
+/// preserving the order they were recorded in.
+pub struct DeferredBuilder {
+    handle: Handle,
+    client: Arc<AspireClient>,
+    mutations: Vec<(String, HashMap<String, Value>)>,
+}
+
+impl DeferredBuilder {
+    pub fn new(handle: Handle, client: Arc<AspireClient>) -> Self {
+        Self {
+            handle,
+            client,
+            mutations: Vec::new(),
+        }
+    }
+
+    /// Queues a `with_*`-style capability call against this handle without
+    /// dispatching it yet. Returns `self` so calls chain the same way the
+    /// eager `with_*` methods do.
+    pub fn queue(mut self, capability_id: impl Into<String>, args: HashMap<String, Value>) -> Self {
+        self.mutations.push((capability_id.into(), args));
+        self
+    }
+
+    /// Flushes every queued mutation as one ordered list for the host to
+    /// replay against the handle, returning the final handle. A chain with no
+    /// queued mutations is a no-op that returns the original handle without a
+    /// round trip.
+    pub fn apply(self) -> Result<Handle, AspireError> {
+        if self.mutations.is_empty() {
+            return Ok(self.handle);
+        }
+
+        let mutations: Vec<Value> = self
+            .mutations
+            .into_iter()
+            .map(|(capability_id, args)| json!({ "capability": capability_id, "args": args }))
+            .collect();
+
+        let mut args = HashMap::new();
+        args.insert("handle".to_string(), self.handle.to_json());
+        args.insert("mutations".to_string(), Value::Array(mutations));
+
+        let result = self.client.invoke_capability("Aspire.Hosting/applyBuilderMutations", args)?;
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
 /// Trait for types that can be serialized to JSON.
 pub trait ToJson {
     fn to_json(&self) -> Value;
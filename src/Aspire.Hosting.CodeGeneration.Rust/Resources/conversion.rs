@@ -0,0 +1,103 @@
+//! Typed value conversions for DTO scalar fields and timestamp setters.
+//!
+//! Generated DTO fields and `with_*` setters mostly take `&str`/`f64` at the
+//! wire boundary (`TestConfigDto::port`, `with_created_at`), leaving callers
+//! to format dates or numbers by hand. `Conversion` names the handful of
+//! scalar shapes those fields actually take and converts a raw string into
+//! the `serde_json::Value` the capability args map expects.
+
+use serde_json::Value;
+
+/// A named scalar conversion, parsed from a descriptor string like `"int"`
+/// or `"timestamp|%Y-%m-%dT%H:%M:%S"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conversion {
+    Bytes,
+    Integer,
+    Float,
+    Boolean,
+    /// RFC3339 or epoch-seconds, parsed into a `chrono::DateTime<Utc>`.
+    Timestamp,
+    /// Naive (no offset) timestamp, parsed with the given strftime pattern.
+    TimestampFmt(String),
+    /// Timestamp with an offset, parsed with the given strftime pattern.
+    TimestampTzFmt(String),
+}
+
+/// Failure converting a raw string through a `Conversion`.
+#[derive(Debug)]
+pub enum ConversionError {
+    /// The descriptor string didn't name a known conversion.
+    UnknownConversion(String),
+    /// `raw` didn't parse under the chosen conversion.
+    Malformed { conversion: String, raw: String },
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownConversion(name) => write!(f, "unknown conversion: {}", name),
+            Self::Malformed { conversion, raw } => write!(f, "'{}' is not a valid {} value", raw, conversion),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl std::str::FromStr for Conversion {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('|') {
+            Some(("timestamp", pattern)) => Ok(Self::TimestampFmt(pattern.to_string())),
+            Some(("timestamptz", pattern)) => Ok(Self::TimestampTzFmt(pattern.to_string())),
+            Some((name, _)) => Err(ConversionError::UnknownConversion(name.to_string())),
+            None => match s {
+                "bytes" => Ok(Self::Bytes),
+                "int" | "integer" => Ok(Self::Integer),
+                "float" => Ok(Self::Float),
+                "bool" | "boolean" => Ok(Self::Boolean),
+                "timestamp" => Ok(Self::Timestamp),
+                other => Err(ConversionError::UnknownConversion(other.to_string())),
+            },
+        }
+    }
+}
+
+impl Conversion {
+    /// Parses `raw` into the `serde_json::Value` shape the capability args
+    /// map expects for this conversion.
+    pub fn convert(&self, raw: &str) -> Result<Value, ConversionError> {
+        let malformed = || ConversionError::Malformed {
+            conversion: format!("{:?}", self),
+            raw: raw.to_string(),
+        };
+        match self {
+            Self::Bytes => Ok(Value::String(raw.to_string())),
+            Self::Integer => raw.parse::<i64>().map(Value::from).map_err(|_| malformed()),
+            Self::Float => raw.parse::<f64>().map(Value::from).map_err(|_| malformed()),
+            Self::Boolean => raw.parse::<bool>().map(Value::Bool).map_err(|_| malformed()),
+            Self::Timestamp => parse_timestamp(raw).map(|dt| Value::String(dt.to_rfc3339())).ok_or_else(malformed),
+            Self::TimestampFmt(pattern) => chrono::NaiveDateTime::parse_from_str(raw, pattern)
+                .map(|dt| Value::String(dt.and_utc().to_rfc3339()))
+                .map_err(|_| malformed()),
+            Self::TimestampTzFmt(pattern) => chrono::DateTime::parse_from_str(raw, pattern)
+                .map(|dt| Value::String(dt.to_rfc3339()))
+                .map_err(|_| malformed()),
+        }
+    }
+}
+
+/// Tries RFC3339 first, then falls back to epoch seconds.
+fn parse_timestamp(raw: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+        return Some(dt.with_timezone(&chrono::Utc));
+    }
+    raw.parse::<i64>().ok().and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+}
+
+/// Serializes `dt` the same way `Conversion::Timestamp` would, for the
+/// generated `with_created_at_dt`-style typed setter overloads.
+pub fn serialize_timestamp(dt: chrono::DateTime<chrono::Utc>) -> String {
+    dt.to_rfc3339()
+}
@@ -0,0 +1,180 @@
+//! Structured error type for the ATS transport and generated bindings.
+
+use serde_json::Value;
+
+/// Error returned by `AspireClient` operations and generated wrapper methods.
+///
+/// Distinguishes a transport-level failure from a cancelled operation or an
+/// exception raised on the host, so callers can `match` instead of
+/// string-matching a boxed error.
+#[derive(Debug)]
+pub enum AspireError {
+    /// A capability was invoked, or a message written/read, while
+    /// `connect()`/`connect_with()` hadn't been called yet or `disconnect()`
+    /// had already torn the connection down — distinct from `Transport` so
+    /// callers can tell "never connected" apart from an I/O failure on a
+    /// live socket.
+    NotConnected,
+    /// I/O failure reading from or writing to the AppHost connection.
+    Transport(std::io::Error),
+    /// The message body could not be encoded/decoded.
+    Serialization(serde_json::Error),
+    /// The host has no capability registered under this name. `server_version`
+    /// is the negotiated AppHost protocol version (see
+    /// `AspireClient::negotiated_protocol`) when the error came from an actual
+    /// `invokeCapability` round trip, so the message can name which server
+    /// build is missing the capability rather than just the capability name.
+    CapabilityNotFound {
+        name: String,
+        server_version: Option<String>,
+    },
+    /// Strict mode (see `AspireClient::enable_strict_mode`) rejected a call
+    /// because `describe()` has no capability by this name, catching a
+    /// typo'd or renamed capability string before it ever reaches the host.
+    UnknownCapability { name: String },
+    /// Strict mode rejected a call because its arguments don't satisfy the
+    /// capability's descriptor (e.g. a required argument is missing).
+    ArgMismatch { name: String, message: String },
+    /// The operation was cancelled locally or via its `CancellationToken`.
+    Canceled,
+    /// A `HandleWrapperBase`/`AspireList`/`AspireDict` held a handle that the
+    /// AppHost no longer recognizes after a reconnect (see
+    /// `AspireClient::on_reconnect`/`with_reconnect`) — the host restarted
+    /// and reissued handles rather than resuming the old session. Distinct
+    /// from `CapabilityNotFound`, which means the call itself isn't
+    /// supported rather than that this particular object reference died.
+    HandleInvalidated { handle_id: String },
+    /// A registered callback panicked while `invoke_callback` ran it. The
+    /// panic is caught at the call site so one misbehaving callback fails
+    /// only the in-flight invocation rather than unwinding the dispatch
+    /// thread and leaving the host's request unanswered.
+    CallbackPanicked { message: String },
+    /// A blocking-query wait (e.g. `IResourceWithWaitSupport::wait_for_healthy`)
+    /// exceeded its caller-supplied overall timeout before the watched state
+    /// index reached the target condition.
+    WaitTimeout {
+        last_index: u64,
+    },
+    /// A JSON-RPC 2.0 `{code, message, data}` error object returned for the
+    /// request itself (malformed method, invalid params, …), as opposed to a
+    /// capability-level `$error` envelope, which surfaces as `Remote`.
+    Rpc {
+        code: i64,
+        message: String,
+        data: Option<Value>,
+    },
+    /// The host capability raised a .NET exception; fields are unmarshalled
+    /// from the `$error` envelope (see `ats_error_codes`).
+    /// `capability` and `type_name` are kept distinct rather than folding one
+    /// into the other when the envelope is missing a .NET exception type, so
+    /// callers can always tell which capability failed even when the host
+    /// didn't report a type name. `data` carries the envelope's structured
+    /// validation payload (if any) so callers like `with_config`/
+    /// `with_validator` can match on it instead of parsing `message`
+    /// themselves.
+    ///
+    /// Boxed because this is by far the largest variant (a handful of
+    /// `String`s plus a `serde_json::Value`); unboxed, it forced every
+    /// `Result<T, AspireError>` in the crate to reserve its size regardless
+    /// of which variant was actually returned, tripping `clippy::result_large_err`.
+    Remote(Box<RemoteError>),
+}
+
+/// Fields of `AspireError::Remote`, boxed out of the enum to keep
+/// `AspireError` small (see the variant's doc comment).
+#[derive(Debug)]
+pub struct RemoteError {
+    pub capability: String,
+    pub type_name: String,
+    pub message: String,
+    pub stack: Option<String>,
+    pub data: Option<Value>,
+}
+
+impl std::fmt::Display for AspireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotConnected => write!(f, "not connected to AppHost"),
+            Self::Transport(e) => write!(f, "transport error: {}", e),
+            Self::Serialization(e) => write!(f, "serialization error: {}", e),
+            Self::CapabilityNotFound { name, server_version: Some(v) } => {
+                write!(f, "capability {} not supported by this AppHost (server v{})", name, v)
+            }
+            Self::CapabilityNotFound { name, server_version: None } => write!(f, "capability not found: {}", name),
+            Self::UnknownCapability { name } => write!(f, "unknown capability: {}", name),
+            Self::ArgMismatch { name, message } => write!(f, "argument mismatch for {}: {}", name, message),
+            Self::Canceled => write!(f, "operation canceled"),
+            Self::HandleInvalidated { handle_id } => write!(f, "handle {} was invalidated by a reconnect", handle_id),
+            Self::CallbackPanicked { message } => write!(f, "callback panicked: {}", message),
+            Self::WaitTimeout { last_index } => write!(f, "wait timed out (last observed index: {})", last_index),
+            Self::Rpc { code, message, .. } => write!(f, "rpc error {}: {}", code, message),
+            Self::Remote(e) => write!(f, "{} ({}): {}", e.capability, e.type_name, e.message),
+        }
+    }
+}
+
+impl std::error::Error for AspireError {}
+
+impl From<std::io::Error> for AspireError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Transport(e)
+    }
+}
+
+impl From<serde_json::Error> for AspireError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialization(e)
+    }
+}
+
+/// Recovers the `AspireError` behind a generated wrapper method's
+/// `Box<dyn std::error::Error>` return (e.g. `with_dependency`,
+/// `with_endpoints`, `create_builder`), so callers can `match` on a
+/// validation error's `code` or a `Transport` drop instead of string-matching
+/// the boxed error's `Display` output. Returns `None` if `error` did not
+/// originate as an `AspireError` (e.g. a future wrapper that boxes some other
+/// error type directly).
+pub fn as_aspire_error<'a>(error: &'a (dyn std::error::Error + 'static)) -> Option<&'a AspireError> {
+    error.downcast_ref::<AspireError>()
+}
+
+/// Parses a JSON-RPC 2.0 `{code, message, data}` error object (the `error`
+/// field of a response envelope) into `AspireError::Rpc`.
+pub fn rpc_error_from_value(error: &Value) -> AspireError {
+    AspireError::Rpc {
+        code: error.get("code").and_then(|v| v.as_i64()).unwrap_or(0),
+        message: error
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown error")
+            .to_string(),
+        data: error.get("data").cloned(),
+    }
+}
+
+/// Builds an `AspireError::Remote` from a capability result's `$error` envelope,
+/// if `value` is one. Returns `None` for an ordinary (non-error) result.
+/// `capability` is the name of the call that produced `value`, since the
+/// envelope itself doesn't always repeat it.
+pub fn remote_error_from_value(capability: &str, value: &Value) -> Option<AspireError> {
+    let obj = value.as_object()?;
+    let err_obj = obj.get("$error")?.as_object()?;
+    Some(AspireError::Remote(Box::new(RemoteError {
+        capability: capability.to_string(),
+        type_name: err_obj
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string(),
+        message: err_obj
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string(),
+        stack: err_obj
+            .get("stack")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        data: err_obj.get("data").cloned(),
+    })))
+}
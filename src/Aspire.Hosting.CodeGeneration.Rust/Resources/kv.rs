@@ -0,0 +1,112 @@
+//! Distributed KV store and session-based locking, modeled on Consul's KV +
+//! session/lock design, for coordinating multiple replicas of a resource.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::AspireError;
+use crate::transport::AspireClient;
+
+/// A KV entry along with the index it was last modified at, used for
+/// compare-and-set writes and lock acquisition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KvEntry {
+    pub value: Value,
+    pub modify_index: u64,
+}
+
+impl AspireClient {
+    /// Writes `value` unconditionally and returns the entry's new modify index.
+    pub fn kv_put(&self, key: &str, value: Value) -> Result<u64, AspireError> {
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), Value::String(key.to_string()));
+        args.insert("value".to_string(), value);
+        let result = self.invoke_capability("Aspire.Hosting/kvPut", args)?;
+        Ok(result.get("modifyIndex").and_then(|v| v.as_u64()).unwrap_or(0))
+    }
+
+    /// Reads the current value and modify index for `key`, or `None` if unset.
+    pub fn kv_get(&self, key: &str) -> Result<Option<KvEntry>, AspireError> {
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), Value::String(key.to_string()));
+        let result = self.invoke_capability("Aspire.Hosting/kvGet", args)?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_value(result)?))
+    }
+
+    /// Writes `value` only if the key's current modify index equals `expected_index`.
+    /// Returns `false` (not an error) on a lost race.
+    pub fn kv_cas(&self, key: &str, value: Value, expected_index: u64) -> Result<bool, AspireError> {
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), Value::String(key.to_string()));
+        args.insert("value".to_string(), value);
+        args.insert("expectedIndex".to_string(), json!(expected_index));
+        let result = self.invoke_capability("Aspire.Hosting/kvCas", args)?;
+        Ok(result.as_bool().unwrap_or(false))
+    }
+
+    /// Returns this client's KV session, creating one with `session_ttl` if needed.
+    fn ensure_kv_session(&self, session_ttl: Duration) -> Result<String, AspireError> {
+        if let Some(id) = self.kv_session_id.lock().unwrap().clone() {
+            return Ok(id);
+        }
+        let mut args = HashMap::new();
+        args.insert("ttlSeconds".to_string(), json!(session_ttl.as_secs()));
+        let result = self.invoke_capability("Aspire.Hosting/kvCreateSession", args)?;
+        let session_id = result
+            .get("sessionId")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        *self.kv_session_id.lock().unwrap() = Some(session_id.clone());
+        Ok(session_id)
+    }
+
+    /// Attempts to acquire a session-backed lock on `key` for leader election.
+    ///
+    /// This is a single atomic host round trip (Consul's lock "acquire"
+    /// semantics), not a client-side `kv_get` + `kv_cas`: the host is the one
+    /// that knows whether an existing holder's session is still alive, so it
+    /// checks that itself rather than racing a client-observed snapshot of
+    /// the key against a second client's concurrent acquire. Returns `false`,
+    /// not an error, when another live session already holds the lock, so
+    /// callers can retry with back-off.
+    pub fn acquire_lock(&self, key: &str, session_ttl: Duration) -> Result<bool, AspireError> {
+        let session_id = self.ensure_kv_session(session_ttl)?;
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), Value::String(key.to_string()));
+        args.insert("session".to_string(), Value::String(session_id));
+        let result = self.invoke_capability("Aspire.Hosting/kvAcquireLock", args)?;
+        Ok(result.as_bool().unwrap_or(false))
+    }
+
+    /// Releases a previously acquired lock, making the key available again.
+    pub fn release_lock(&self, key: &str) -> Result<(), AspireError> {
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), Value::String(key.to_string()));
+        self.invoke_capability("Aspire.Hosting/kvReleaseLock", args)?;
+        Ok(())
+    }
+
+    /// Renews this client's KV session so locks it holds don't expire.
+    pub fn renew_session(&self) -> Result<(), AspireError> {
+        let session_id = self
+            .kv_session_id
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| AspireError::CapabilityNotFound {
+                name: "Aspire.Hosting/kvRenewSession (no active session)".to_string(),
+                server_version: None,
+            })?;
+        let mut args = HashMap::new();
+        args.insert("sessionId".to_string(), Value::String(session_id));
+        self.invoke_capability("Aspire.Hosting/kvRenewSession", args)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+//! Opt-in encrypted, authenticated transport, gated behind the `security`
+//! feature.
+//!
+//! The default stack (see `transport.rs`) sends `Content-Length`-framed JSON
+//! in cleartext over whatever byte stream `TransportKind` opens — a Unix
+//! socket, a Windows named pipe, or a TCP connection. That's fine when the
+//! AppHost endpoint is only reachable by the user who started it, but a
+//! shared machine (a CI runner, a devcontainer with other tenants) can have
+//! other local users who can open the same socket or port. `SecureTransport`
+//! wraps any already-open `Transport` in a Noise handshake keyed by a
+//! pre-shared key, then encrypts every byte that crosses it from that point
+//! on — `AspireClient` doesn't know the difference, since `SecureTransport`
+//! still only exposes `write_all`/`flush`/`read_line`/`read_exact`/`split`,
+//! the same interface `TcpTransport`/`StdioTransport`/`GrpcTransport` do.
+//!
+//! The handshake uses `Noise_NNpsk0_25519_ChaChaPoly_BLAKE2s`: no static
+//! keypairs to provision, just the 32-byte PSK both sides already share out
+//! of band (e.g. a secret the AppHost wrote to disk alongside the socket
+//! path). `AspireClient::with_security` is the initiator; the AppHost is
+//! always the responder. If the peer doesn't complete the handshake — wrong
+//! PSK, a plaintext-speaking peer that doesn't understand Noise frames at
+//! all, or the connection drops mid-handshake — `open_connection` fails
+//! closed: there is no fallback to the unencrypted path once `with_security`
+//! has been asked for, only an error.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::transport::{Transport, TransportRead, TransportWrite};
+
+/// Pre-shared key material for `AspireClient::with_security`'s handshake.
+/// The same 32 bytes must be configured on the AppHost side; there is no
+/// negotiation of the key itself, only of the session keys the handshake
+/// derives from it.
+#[derive(Clone)]
+pub struct SecurityConfig {
+    pub psk: [u8; 32],
+}
+
+/// Largest plaintext chunk encrypted into a single Noise transport message.
+/// Noise caps an encoded message at 65535 bytes; `ChaChaPoly`'s 16-byte tag
+/// comes out of that budget, leaving this much room for plaintext.
+const MAX_PLAINTEXT_CHUNK: usize = 65535 - 16;
+
+const NOISE_PATTERN: &str = "Noise_NNpsk0_25519_ChaChaPoly_BLAKE2s";
+
+/// Performs the initiator side of the handshake over `raw` (still
+/// unencrypted at this point — the handshake messages themselves are framed
+/// with a plain 4-byte big-endian length prefix, not yet the record layer
+/// `SecureTransport` builds on top once this returns), then wraps it in a
+/// `SecureTransport` ready for `AspireClient::connect` to `split()`.
+///
+/// `Noise_NNpsk0` is a two-message pattern (`-> psk, e` / `<- e, ee`), so the
+/// initiator's turn is always the odd step and the responder's the even one;
+/// there's no dynamic `is_my_turn()` query needed for this one fixed pattern.
+pub fn handshake(mut raw: Box<dyn Transport>, config: &SecurityConfig) -> Result<Box<dyn Transport>, Box<dyn std::error::Error>> {
+    let mut state = snow::Builder::new(NOISE_PATTERN.parse()?)
+        .psk(0, &config.psk)
+        .build_initiator()?;
+
+    let mut buf = vec![0u8; 65535];
+
+    let len = state.write_message(&[], &mut buf)?;
+    write_framed(raw.as_mut(), &buf[..len])?;
+
+    let frame = read_framed(raw.as_mut())?;
+    state.read_message(&frame, &mut buf)?;
+
+    if !state.is_handshake_finished() {
+        return Err("Noise handshake did not complete in the expected two messages".into());
+    }
+
+    let transport_state = state.into_transport_mode()?;
+    Ok(Box::new(SecureTransport {
+        inner: raw,
+        state: Arc::new(Mutex::new(transport_state)),
+    }))
+}
+
+fn write_framed(transport: &mut dyn Transport, payload: &[u8]) -> std::io::Result<()> {
+    transport.write_all(&(payload.len() as u32).to_be_bytes())?;
+    transport.write_all(payload)?;
+    transport.flush()
+}
+
+fn read_framed(transport: &mut dyn Transport) -> std::io::Result<Vec<u8>> {
+    let mut len_bytes = [0u8; 4];
+    transport.read_exact(&mut len_bytes)?;
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    let mut payload = vec![0u8; len];
+    transport.read_exact(&mut payload)?;
+    Ok(payload)
+}
+
+/// Wraps an already-handshaken `Transport` in an encrypted record layer.
+/// Every `write_all` is sealed into one or more length-prefixed Noise
+/// transport messages; every `read_line`/`read_exact` is served out of a
+/// decrypted plaintext buffer that's refilled one record at a time as it
+/// runs dry. `state` carries its own per-direction nonce counters, but since
+/// `snow::TransportState::write_message`/`read_message` both take `&mut
+/// self`, the two directions still serialize through one `Mutex` rather than
+/// running truly concurrently — the same tradeoff `SharedHalf` documents for
+/// transports whose read/write sides aren't independent OS handles.
+struct SecureTransport {
+    inner: Box<dyn Transport>,
+    state: Arc<Mutex<snow::TransportState>>,
+}
+
+impl Transport for SecureTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        for chunk in buf.chunks(MAX_PLAINTEXT_CHUNK) {
+            let mut ciphertext = vec![0u8; chunk.len() + 16];
+            let len = self
+                .state
+                .lock()
+                .unwrap()
+                .write_message(chunk, &mut ciphertext)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+            write_framed(self.inner.as_mut(), &ciphertext[..len])?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+
+    fn read_line(&mut self, _buf: &mut String) -> std::io::Result<usize> {
+        unreachable!("SecureTransport is only used pre-split; see SecureRead::read_line")
+    }
+
+    fn read_exact(&mut self, _buf: &mut [u8]) -> std::io::Result<()> {
+        unreachable!("SecureTransport is only used pre-split; see SecureRead::read_exact")
+    }
+
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        let (inner_read, inner_write) = self.inner.split();
+        let read = SecureRead {
+            inner: inner_read,
+            state: self.state.clone(),
+            plaintext: VecDeque::new(),
+        };
+        let write = SecureWrite {
+            inner: inner_write,
+            state: self.state,
+        };
+        (Box::new(read), Box::new(write))
+    }
+}
+
+struct SecureWrite {
+    inner: Box<dyn TransportWrite>,
+    state: Arc<Mutex<snow::TransportState>>,
+}
+
+impl TransportWrite for SecureWrite {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        for chunk in buf.chunks(MAX_PLAINTEXT_CHUNK) {
+            let mut ciphertext = vec![0u8; chunk.len() + 16];
+            let len = self
+                .state
+                .lock()
+                .unwrap()
+                .write_message(chunk, &mut ciphertext)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+            self.inner.write_all(&(len as u32).to_be_bytes())?;
+            self.inner.write_all(&ciphertext[..len])?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+struct SecureRead {
+    inner: Box<dyn TransportRead>,
+    state: Arc<Mutex<snow::TransportState>>,
+    /// Decrypted bytes from the most recently read record that haven't been
+    /// handed to a `read_line`/`read_exact` caller yet. Needed because a
+    /// Noise record's plaintext rarely lines up with the `\n`-terminated
+    /// header lines and fixed-size bodies `read_message`/`write_message`
+    /// (transport.rs) ask for.
+    plaintext: VecDeque<u8>,
+}
+
+impl SecureRead {
+    fn fill(&mut self) -> std::io::Result<()> {
+        let mut len_bytes = [0u8; 4];
+        self.inner.read_exact(&mut len_bytes)?;
+        let len = u32::from_be_bytes(len_bytes) as usize;
+        let mut ciphertext = vec![0u8; len];
+        self.inner.read_exact(&mut ciphertext)?;
+
+        let mut plaintext = vec![0u8; len];
+        let written = self
+            .state
+            .lock()
+            .unwrap()
+            .read_message(&ciphertext, &mut plaintext)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+        self.plaintext.extend(&plaintext[..written]);
+        Ok(())
+    }
+}
+
+impl TransportRead for SecureRead {
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        loop {
+            if let Some(pos) = self.plaintext.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.plaintext.drain(..=pos).collect();
+                let text = String::from_utf8(line).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                let n = text.len();
+                buf.push_str(&text);
+                return Ok(n);
+            }
+            self.fill()?;
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        while self.plaintext.len() < buf.len() {
+            self.fill()?;
+        }
+        for slot in buf.iter_mut() {
+            *slot = self.plaintext.pop_front().unwrap();
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
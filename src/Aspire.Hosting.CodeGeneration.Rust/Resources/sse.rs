@@ -0,0 +1,140 @@
+//! Server-Sent-Events client for host-pushed data (resource logs, status
+//! transitions) that the request/response ATS transport has no way to carry.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc;
+
+use serde_json::Value;
+
+use crate::error::AspireError;
+
+/// An iterator over JSON events streamed from an SSE endpoint.
+///
+/// A background thread owns the HTTP connection, reassembles multi-line
+/// `data:` fields, and reconnects with `Last-Event-ID` if the host drops the
+/// connection. `next()` blocks until the next event is available.
+pub struct SseStream {
+    receiver: mpsc::Receiver<Result<Value, AspireError>>,
+}
+
+impl SseStream {
+    pub(crate) fn connect(base_url: &str, capability_id: &str, args: &Value) -> Result<Self, AspireError> {
+        let url = format!(
+            "{}/capabilities/{}?args={}",
+            base_url.trim_end_matches('/'),
+            capability_id,
+            urlencode(&args.to_string())
+        );
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            if let Err(e) = run_sse_loop(&url, &tx) {
+                let _ = tx.send(Err(e));
+            }
+        });
+        Ok(Self { receiver: rx })
+    }
+}
+
+impl Iterator for SseStream {
+    type Item = Result<Value, AspireError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+fn run_sse_loop(url: &str, tx: &mpsc::Sender<Result<Value, AspireError>>) -> Result<(), AspireError> {
+    let (host, port, path) = parse_http_url(url)?;
+    let mut last_event_id: Option<String> = None;
+
+    loop {
+        let stream = TcpStream::connect((host.as_str(), port)).map_err(AspireError::Transport)?;
+        let mut writer = stream.try_clone().map_err(AspireError::Transport)?;
+
+        let mut request = format!(
+            "GET {path} HTTP/1.1\r\nHost: {host}\r\nAccept: text/event-stream\r\nConnection: keep-alive\r\n"
+        );
+        if let Some(id) = &last_event_id {
+            request.push_str(&format!("Last-Event-ID: {}\r\n", id));
+        }
+        request.push_str("\r\n");
+        writer.write_all(request.as_bytes()).map_err(AspireError::Transport)?;
+
+        let mut reader = BufReader::new(stream);
+        if !skip_http_headers(&mut reader)? {
+            continue;
+        }
+
+        let mut data_lines: Vec<String> = Vec::new();
+        let mut event_id: Option<String> = None;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line).map_err(AspireError::Transport)?;
+            if read == 0 {
+                break; // connection dropped; reconnect below using last_event_id
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if line.is_empty() {
+                if !data_lines.is_empty() {
+                    let payload = data_lines.join("\n");
+                    data_lines.clear();
+                    if event_id.is_some() {
+                        last_event_id = event_id.take();
+                    }
+                    let value: Value = serde_json::from_str(&payload)?;
+                    if tx.send(Ok(value)).is_err() {
+                        return Ok(()); // receiver dropped; stop streaming
+                    }
+                }
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("data:") {
+                data_lines.push(rest.trim_start().to_string());
+            } else if let Some(rest) = line.strip_prefix("id:") {
+                event_id = Some(rest.trim_start().to_string());
+            }
+            // `event:` lines are ignored: every payload here is already a
+            // self-describing JSON value.
+        }
+    }
+}
+
+/// Consumes the HTTP status line and headers, leaving the reader positioned
+/// at the start of the event-stream body. Returns `false` if the connection
+/// closed before the headers finished, so the caller can reconnect.
+fn skip_http_headers(reader: &mut impl BufRead) -> Result<bool, AspireError> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).map_err(AspireError::Transport)?;
+        if read == 0 {
+            return Ok(false);
+        }
+        if line == "\r\n" || line == "\n" {
+            return Ok(true);
+        }
+    }
+}
+
+fn parse_http_url(url: &str) -> Result<(String, u16, String), AspireError> {
+    let without_scheme = url.strip_prefix("http://").unwrap_or(url);
+    let (authority, path) = without_scheme.split_once('/').unwrap_or((without_scheme, ""));
+    let (host, port) = authority.split_once(':').unwrap_or((authority, "80"));
+    let port = port.parse().unwrap_or(80);
+    Ok((host.to_string(), port, format!("/{}", path)))
+}
+
+fn urlencode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
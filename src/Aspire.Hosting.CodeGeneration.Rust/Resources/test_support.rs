@@ -0,0 +1,606 @@
+//! In-process fake `AspireClient` for unit-testing generated bindings
+//! without a live .NET AppHost, gated behind the `test-support` feature.
+#![cfg(feature = "test-support")]
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+
+use crate::transport::{self, ats_error_codes, AspireClient, SharedHalf, Transport, TransportRead, TransportWrite};
+use crate::wire_format::JsonWireFormat;
+
+/// One observed `invoke_capability` call (or direct transport RPC like
+/// `cancelToken`), in the order it was made.
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    pub capability_id: String,
+    pub args: HashMap<String, Value>,
+}
+
+enum CannedResponse {
+    Value(Value),
+    Handler(Box<dyn Fn(&HashMap<String, Value>) -> Value + Send + Sync>),
+    /// Consumed front-to-back, one value per call; the last value repeats
+    /// once the queue runs dry so a test doesn't need to size it exactly.
+    Sequence(VecDeque<Value>),
+}
+
+struct FakeHostState {
+    responses: HashMap<String, CannedResponse>,
+    calls: Vec<RecordedCall>,
+}
+
+impl FakeHostState {
+    fn handle_message(&mut self, message: Value) -> Value {
+        match message {
+            Value::Array(entries) => Value::Array(entries.into_iter().map(|m| self.handle_single(m)).collect()),
+            other => self.handle_single(other),
+        }
+    }
+
+    fn handle_single(&mut self, message: Value) -> Value {
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let method = message.get("method").and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+        if method == "invokeCapability" {
+            let params = message.get("params").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            let capability_id = params.get(0).and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let args: HashMap<String, Value> = params
+                .get(1)
+                .and_then(|v| v.as_object())
+                .map(|obj| obj.clone().into_iter().collect())
+                .unwrap_or_default();
+
+            self.calls.push(RecordedCall { capability_id: capability_id.clone(), args: args.clone() });
+
+            let result = match self.responses.get_mut(&capability_id) {
+                Some(CannedResponse::Value(value)) => value.clone(),
+                Some(CannedResponse::Handler(handler)) => handler(&args),
+                Some(CannedResponse::Sequence(queue)) => {
+                    if queue.len() > 1 { queue.pop_front().unwrap() } else { queue.front().cloned().unwrap_or(Value::Null) }
+                }
+                None => json!({
+                    "$error": {
+                        "code": ats_error_codes::CAPABILITY_NOT_FOUND,
+                        "capability": capability_id,
+                        "message": format!(
+                            "FakeHost has no canned response for `{}`; register one with `FakeHost::on`/`on_fn`",
+                            capability_id
+                        ),
+                    }
+                }),
+            };
+            return json!({ "jsonrpc": "2.0", "id": id, "result": result });
+        }
+
+        // Direct transport-level RPCs (`negotiateWireFormat`, `cancelToken`, ...)
+        // are keyed by method name in the same registry, so tests can override
+        // them too; unregistered ones default to a harmless success.
+        self.calls.push(RecordedCall { capability_id: method.clone(), args: HashMap::new() });
+        let result = match self.responses.get_mut(&method) {
+            Some(CannedResponse::Value(value)) => value.clone(),
+            Some(CannedResponse::Handler(handler)) => handler(&HashMap::new()),
+            Some(CannedResponse::Sequence(queue)) => {
+                if queue.len() > 1 { queue.pop_front().unwrap() } else { queue.front().cloned().unwrap_or(Value::Null) }
+            }
+            None if method == "cancelToken" => Value::Bool(true),
+            None => Value::Null,
+        };
+        json!({ "jsonrpc": "2.0", "id": id, "result": result })
+    }
+}
+
+/// Drives a `FakeTransport`-backed `AspireClient` from test code: register
+/// canned responses per capability name, inspect the ordered call log, and
+/// push `invokeCallback`/notification frames to exercise callback- and
+/// subscription-taking methods (`with_validator`, `subscribe`, ...) without a
+/// live .NET host.
+#[derive(Clone)]
+pub struct FakeHost {
+    state: Arc<Mutex<FakeHostState>>,
+}
+
+impl FakeHost {
+    fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FakeHostState {
+                responses: HashMap::new(),
+                calls: Vec::new(),
+            })),
+        }
+    }
+
+    /// Answers every call to `capability_id` with the fixed `value`.
+    pub fn on(&self, capability_id: &str, value: Value) {
+        self.state.lock().unwrap().responses.insert(capability_id.to_string(), CannedResponse::Value(value));
+    }
+
+    /// Answers every call to `capability_id` with the fixed `handle`, as most
+    /// capabilities that create or return a resource do.
+    pub fn on_handle(&self, capability_id: &str, handle: transport::Handle) {
+        self.on(capability_id, handle.to_json());
+    }
+
+    /// Answers successive calls to `capability_id` with `values` in order,
+    /// one per call, for a builder chain that calls the same capability more
+    /// than once (e.g. repeated `add_test_redis` calls) and expects a
+    /// distinct handle back each time rather than the one fixed value `on`
+    /// would give every call. The last value repeats once `values` is
+    /// exhausted, so tests don't need to size it exactly.
+    pub fn on_sequence(&self, capability_id: &str, values: impl IntoIterator<Item = Value>) {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .insert(capability_id.to_string(), CannedResponse::Sequence(values.into_iter().collect()));
+    }
+
+    /// Answers every call to `capability_id` by invoking `handler` with the
+    /// decoded args map, for responses that depend on what was passed in.
+    pub fn on_fn(
+        &self,
+        capability_id: &str,
+        handler: impl Fn(&HashMap<String, Value>) -> Value + Send + Sync + 'static,
+    ) {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .insert(capability_id.to_string(), CannedResponse::Handler(Box::new(handler)));
+    }
+
+    /// Returns every recorded call, in the order it was made.
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Asserts that `capability_id` was invoked at least once and runs
+    /// `check` against the args of its first recorded call, so a test can
+    /// write `host.assert_capability_invoked("…/withConfig", |args| assert_eq!(...))`
+    /// instead of filtering `calls()` by hand. Panics with the full call log
+    /// if `capability_id` was never invoked.
+    pub fn assert_capability_invoked(&self, capability_id: &str, check: impl FnOnce(&HashMap<String, Value>)) {
+        let calls = self.calls();
+        let call = calls.iter().find(|call| call.capability_id == capability_id).unwrap_or_else(|| {
+            panic!(
+                "expected `{}` to have been invoked, but it wasn't; recorded calls: {:?}",
+                capability_id, calls
+            )
+        });
+        check(&call.args);
+    }
+
+    /// Invokes the callback registered under `callback_id` (e.g. the
+    /// `CallbackGuard::id()` passed as a `"validator"`/`"operation"`
+    /// argument) the same way a real host would, and returns its result.
+    pub fn invoke_callback(&self, callback_id: &str, positional_args: Vec<Value>) -> Option<Value> {
+        transport::invoke_registered_callback(callback_id, positional_args)
+    }
+
+    /// Delivers a host-pushed notification for `subscription_id` (a
+    /// `subscribe`/`subscribe_stream` id), the same no-reply frame a real
+    /// `IDistributedApplicationEventing::subscribe` notification uses.
+    pub fn push_notification(&self, subscription_id: &str, payload: Value) -> bool {
+        transport::push_test_notification(subscription_id, payload)
+    }
+}
+
+/// In-process `Transport` that answers each request synchronously against a
+/// `FakeHostState` instead of reading/writing a real byte stream.
+struct FakeTransport {
+    state: Arc<Mutex<FakeHostState>>,
+    write_buf: Vec<u8>,
+    read_buf: VecDeque<u8>,
+}
+
+impl Transport for FakeTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.write_buf.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        while let Some((message, consumed)) = parse_frame(&self.write_buf) {
+            self.write_buf.drain(..consumed);
+            let response = self.state.lock().unwrap().handle_message(message);
+            let body = serde_json::to_vec(&response).unwrap_or_default();
+            self.read_buf.extend(format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes());
+            self.read_buf.extend(body);
+        }
+        Ok(())
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        let mut count = 0;
+        while let Some(byte) = self.read_buf.pop_front() {
+            count += 1;
+            buf.push(byte as char);
+            if byte == b'\n' {
+                break;
+            }
+        }
+        Ok(count)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        if self.read_buf.len() < buf.len() {
+            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "FakeTransport buffer underrun"));
+        }
+        for slot in buf.iter_mut() {
+            *slot = self.read_buf.pop_front().unwrap();
+        }
+        Ok(())
+    }
+
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        // Reads and writes both go through `handle_message`/`flush` against the
+        // same `FakeHostState`, so there's no independent read/write side to
+        // split apart the way a real socket has — fall back to sharing one
+        // `Mutex`, same as any other non-independently-splittable backend.
+        SharedHalf::pair(*self)
+    }
+}
+
+/// Finds the first complete `Content-Length`-framed message in `buf` and
+/// returns it alongside the number of bytes it consumed, mirroring
+/// `AspireClient::read_message`'s framing so the fake and the real transport
+/// agree on wire shape.
+fn parse_frame(buf: &[u8]) -> Option<(Value, usize)> {
+    let header_end = buf.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let header = std::str::from_utf8(&buf[..header_end]).ok()?;
+    let content_length: usize = header
+        .lines()
+        .find_map(|line| line.strip_prefix("Content-Length:"))
+        .and_then(|v| v.trim().parse().ok())?;
+
+    let body_start = header_end + 4;
+    let body_end = body_start + content_length;
+    if buf.len() < body_end {
+        return None;
+    }
+
+    let value = serde_json::from_slice(&buf[body_start..body_end]).ok()?;
+    Some((value, body_end))
+}
+
+/// Creates an `AspireClient` wired to an in-process `FakeHost` instead of a
+/// live .NET AppHost. Exercise generated wrapper methods against it exactly
+/// as you would a real client — register responses on the `FakeHost` before
+/// calling into the wrapper, then assert on `FakeHost::calls()`.
+pub fn fake_client() -> (Arc<AspireClient>, FakeHost) {
+    let host = FakeHost::new();
+    let transport = FakeTransport {
+        state: host.state.clone(),
+        write_buf: Vec::new(),
+        read_buf: VecDeque::new(),
+    };
+    let client = AspireClient::with_transport(Box::new(transport), Box::new(JsonWireFormat));
+    (client, host)
+}
+
+/// One recorded `invokeCapability` round trip: the capability name, the
+/// decoded args it was called with, and the raw `result` the host returned.
+/// Persisted as one JSON object per line by `RecordingTransport`, and
+/// consumed the same way by `ReplayTransport::load`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RecordedEntry {
+    pub capability: String,
+    pub args: HashMap<String, Value>,
+    pub result: Value,
+}
+
+/// Wraps a real `Transport` and appends every `(capability, args, result)`
+/// triple it observes to a newline-delimited JSON file at `log_path`, so a
+/// real AppHost session can be captured once and replayed offline later via
+/// `ReplayTransport`. Reads and writes pass through to the inner transport
+/// unchanged; this only tees the bytes to recover message boundaries (via
+/// `parse_frame`, the same framing `FakeTransport` relies on) and match each
+/// response back to the request that produced it.
+pub struct RecordingTransport {
+    inner: Box<dyn Transport>,
+    log_path: std::path::PathBuf,
+    pending: HashMap<u64, (String, HashMap<String, Value>)>,
+    write_tee: Vec<u8>,
+    read_tee: Vec<u8>,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Box<dyn Transport>, log_path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            inner,
+            log_path: log_path.into(),
+            pending: HashMap::new(),
+            write_tee: Vec::new(),
+            read_tee: Vec::new(),
+        }
+    }
+
+    /// Notes an outgoing `invokeCapability` request's id, capability, and
+    /// args so `drain_read_frames` can pair it with its eventual response.
+    /// Anything else (batches, direct transport RPCs) is left unrecorded.
+    fn record_request(&mut self, message: &Value) {
+        if message.get("method").and_then(|v| v.as_str()) != Some("invokeCapability") {
+            return;
+        }
+        let Some(id) = message.get("id").and_then(|v| v.as_u64()) else { return };
+        let params = message.get("params").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        let capability = params.get(0).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let args: HashMap<String, Value> = params
+            .get(1)
+            .and_then(|v| v.as_object())
+            .map(|obj| obj.clone().into_iter().collect())
+            .unwrap_or_default();
+        self.pending.insert(id, (capability, args));
+    }
+
+    /// Consumes every complete frame now sitting in `read_tee`, matching
+    /// responses against `pending` by request id and appending the completed
+    /// triple to `log_path`. Requests with no matching entry here (batches,
+    /// callback invocations, notifications) produce no recording.
+    fn drain_read_frames(&mut self) {
+        while let Some((message, consumed)) = parse_frame(&self.read_tee) {
+            self.read_tee.drain(..consumed);
+            let Some(id) = message.get("id").and_then(|v| v.as_u64()) else { continue };
+            let Some((capability, args)) = self.pending.remove(&id) else { continue };
+            let result = message.get("result").cloned().unwrap_or(Value::Null);
+            self.append_entry(&RecordedEntry { capability, args, result });
+        }
+    }
+
+    fn append_entry(&self, entry: &RecordedEntry) {
+        let Ok(line) = serde_json::to_string(entry) else { return };
+        if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(&self.log_path) {
+            use std::io::Write as _;
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.write_tee.extend_from_slice(buf);
+        self.inner.write_all(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        while let Some((message, consumed)) = parse_frame(&self.write_tee) {
+            self.write_tee.drain(..consumed);
+            self.record_request(&message);
+        }
+        self.inner.flush()
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        let start = buf.len();
+        let n = self.inner.read_line(buf)?;
+        self.read_tee.extend_from_slice(buf[start..].as_bytes());
+        self.drain_read_frames();
+        Ok(n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.inner.read_exact(buf)?;
+        self.read_tee.extend_from_slice(buf);
+        self.drain_read_frames();
+        Ok(())
+    }
+
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        // Same rationale as `FakeTransport::split`: the tee state lives on
+        // one side, so both halves share it behind a `Mutex`.
+        SharedHalf::pair(*self)
+    }
+}
+
+/// In-process `Transport` that answers `invokeCapability` calls from a set
+/// of `RecordedEntry` values loaded from a `RecordingTransport` log, instead
+/// of a live host. A call whose capability name and args don't match any
+/// recorded entry fails with `CAPABILITY_NOT_FOUND` rather than hanging,
+/// so a test exercising a code path the recording never covered fails loudly.
+struct ReplayTransport {
+    entries: Vec<RecordedEntry>,
+    write_buf: Vec<u8>,
+    read_buf: VecDeque<u8>,
+}
+
+impl ReplayTransport {
+    /// Loads a newline-delimited JSON log written by `RecordingTransport`.
+    pub fn load(log_path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(log_path)?;
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        Ok(Self {
+            entries,
+            write_buf: Vec::new(),
+            read_buf: VecDeque::new(),
+        })
+    }
+
+    fn find_result(&self, capability: &str, args: &HashMap<String, Value>) -> Option<Value> {
+        self.entries
+            .iter()
+            .find(|entry| entry.capability == capability && &entry.args == args)
+            .map(|entry| entry.result.clone())
+    }
+
+    fn handle_message(&mut self, message: Value) -> Value {
+        match message {
+            Value::Array(entries) => Value::Array(entries.into_iter().map(|m| self.handle_single(m)).collect()),
+            other => self.handle_single(other),
+        }
+    }
+
+    fn handle_single(&mut self, message: Value) -> Value {
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let method = message.get("method").and_then(|v| v.as_str()).unwrap_or("");
+
+        if method == "invokeCapability" {
+            let params = message.get("params").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            let capability = params.get(0).and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let args: HashMap<String, Value> = params
+                .get(1)
+                .and_then(|v| v.as_object())
+                .map(|obj| obj.clone().into_iter().collect())
+                .unwrap_or_default();
+
+            let result = self.find_result(&capability, &args).unwrap_or_else(|| {
+                json!({
+                    "$error": {
+                        "code": ats_error_codes::CAPABILITY_NOT_FOUND,
+                        "capability": capability,
+                        "message": format!(
+                            "ReplayTransport has no recorded call matching `{}` with these args",
+                            capability
+                        ),
+                    }
+                })
+            });
+            return json!({ "jsonrpc": "2.0", "id": id, "result": result });
+        }
+
+        json!({ "jsonrpc": "2.0", "id": id, "result": Value::Null })
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.write_buf.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        while let Some((message, consumed)) = parse_frame(&self.write_buf) {
+            self.write_buf.drain(..consumed);
+            let response = self.handle_message(message);
+            let body = serde_json::to_vec(&response).unwrap_or_default();
+            self.read_buf.extend(format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes());
+            self.read_buf.extend(body);
+        }
+        Ok(())
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        let mut count = 0;
+        while let Some(byte) = self.read_buf.pop_front() {
+            count += 1;
+            buf.push(byte as char);
+            if byte == b'\n' {
+                break;
+            }
+        }
+        Ok(count)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        if self.read_buf.len() < buf.len() {
+            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "ReplayTransport buffer underrun"));
+        }
+        for slot in buf.iter_mut() {
+            *slot = self.read_buf.pop_front().unwrap();
+        }
+        Ok(())
+    }
+
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        SharedHalf::pair(*self)
+    }
+}
+
+/// Creates an `AspireClient` that answers capability calls from a log
+/// previously captured by wrapping a real transport in `RecordingTransport`,
+/// instead of either a live host or a hand-registered `FakeHost`. Lets a
+/// real app-model build be captured once and replayed deterministically and
+/// offline afterward, including the exact argument payloads each generated
+/// `with_*` call emitted.
+pub fn replay_client(log_path: impl AsRef<std::path::Path>) -> std::io::Result<Arc<AspireClient>> {
+    let transport = ReplayTransport::load(log_path)?;
+    Ok(AspireClient::with_transport(Box::new(transport), Box::new(JsonWireFormat)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_client_answers_canned_response_and_records_the_call() {
+        let (client, host) = fake_client();
+        host.on("test/echo", json!({ "ok": true }));
+
+        let result = client.invoke_capability("test/echo", HashMap::new()).unwrap();
+
+        assert_eq!(result, json!({ "ok": true }));
+        let calls = host.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].capability_id, "test/echo");
+    }
+
+    #[test]
+    fn on_sequence_answers_each_call_in_order_then_repeats_the_last_value() {
+        let (client, host) = fake_client();
+        host.on_sequence("test/addRedis", [json!({ "id": 1 }), json!({ "id": 2 })]);
+
+        let first = client.invoke_capability("test/addRedis", HashMap::new()).unwrap();
+        let second = client.invoke_capability("test/addRedis", HashMap::new()).unwrap();
+        let third = client.invoke_capability("test/addRedis", HashMap::new()).unwrap();
+
+        assert_eq!(first, json!({ "id": 1 }));
+        assert_eq!(second, json!({ "id": 2 }));
+        assert_eq!(third, json!({ "id": 2 }));
+    }
+
+    #[test]
+    fn assert_capability_invoked_checks_the_first_matching_call() {
+        let (client, host) = fake_client();
+        host.on("test/withConfig", Value::Null);
+
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), json!("replicas"));
+        client.invoke_capability("test/withConfig", args).unwrap();
+
+        host.assert_capability_invoked("test/withConfig", |args| {
+            assert_eq!(args.get("key"), Some(&json!("replicas")));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `test/neverCalled` to have been invoked")]
+    fn assert_capability_invoked_panics_when_never_called() {
+        let (_client, host) = fake_client();
+        host.assert_capability_invoked("test/neverCalled", |_| {});
+    }
+
+    #[test]
+    fn recording_transport_round_trips_through_replay_transport() {
+        let log_path = std::env::temp_dir().join(format!(
+            "aspire-test-support-recording-{:?}.jsonl",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&log_path);
+
+        let host = FakeHost::new();
+        host.on("test/addRedis", json!({ "id": "redis-1" }));
+        let inner = FakeTransport {
+            state: host.state.clone(),
+            write_buf: Vec::new(),
+            read_buf: VecDeque::new(),
+        };
+        let recording = RecordingTransport::new(Box::new(inner), log_path.clone());
+        let recorder = AspireClient::with_transport(Box::new(recording), Box::new(JsonWireFormat));
+
+        let mut args = HashMap::new();
+        args.insert("name".to_string(), json!("cache"));
+        let recorded_result = recorder.invoke_capability("test/addRedis", args.clone()).unwrap();
+
+        let replayer = replay_client(&log_path).unwrap();
+        let replayed_result = replayer.invoke_capability("test/addRedis", args).unwrap();
+
+        assert_eq!(recorded_result, replayed_result);
+        assert_eq!(replayed_result, json!({ "id": "redis-1" }));
+
+        let _ = std::fs::remove_file(&log_path);
+    }
+}
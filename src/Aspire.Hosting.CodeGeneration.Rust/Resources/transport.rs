@@ -1,18 +1,439 @@
 //! Aspire ATS transport layer for JSON-RPC communication.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufReader, Read, Write};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-// Platform-specific connection type
-#[cfg(target_os = "windows")]
-type Connection = std::fs::File;
+use crate::error::{AspireError, RemoteError};
+use crate::wire_format::{JsonWireFormat, WireFormat};
+
+/// A byte-stream connection an `AspireClient` sends/receives framed
+/// JSON-RPC messages over. Modeled on a debug-adapter-style client:
+/// `AspireClient` only ever calls these methods, so it doesn't care
+/// whether the AppHost is reached via a local socket, a TCP connection to a
+/// remote host daemon, or a spawned subprocess's stdio.
+///
+/// A `read_line`/`read_exact` returning `Ok(0)`/`UnexpectedEof` signals the
+/// peer closed the connection (EOF) or the pipe broke; callers surface this
+/// as `AspireError::Transport` and can match on `.kind()` to distinguish it
+/// from other I/O failures and unregister any callbacks tied to the session.
+pub trait Transport: Send {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()>;
+    fn flush(&mut self) -> std::io::Result<()>;
+    /// Reads a single `\n`-terminated line (including the terminator) into `buf`.
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize>;
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()>;
+
+    /// Splits this transport into independently-lockable read and write
+    /// halves. `AspireClient` locks each half separately so a read blocked
+    /// waiting on the *next* host frame never stalls a write the connection's
+    /// reader isn't itself holding up on purpose (e.g. a callback response
+    /// the read loop just dispatched to a background thread) — see
+    /// `AspireClient::connect`. Backends whose reader/writer are already
+    /// distinct OS handles (sockets, pipes, the gRPC stream) split into truly
+    /// independent halves; `FakeTransport` (test-support) shares one `Mutex`
+    /// since its read and write sides aren't really concurrent.
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>);
+}
+
+/// Read half of a split `Transport` (see `Transport::split`).
+pub trait TransportRead: Send {
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize>;
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()>;
+
+    /// Lets `AspireClient::as_raw_fd` recover the concrete reader behind this
+    /// trait object (e.g. `BufReader<UnixStream>`) without every backend
+    /// having to implement raw-fd exposure itself; backends with no OS file
+    /// descriptor (the gRPC stream) simply have nothing downcastable to a
+    /// socket/pipe type, so the lookup falls through to `None`.
+    fn as_any(&self) -> &dyn std::any::Any;
+}
+
+/// Write half of a split `Transport` (see `Transport::split`).
+pub trait TransportWrite: Send {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()>;
+    fn flush(&mut self) -> std::io::Result<()>;
+}
+
+impl<R: BufRead + Send + 'static> TransportRead for R {
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        BufRead::read_line(self, buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        Read::read_exact(self, buf)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<W: Write + Send> TransportWrite for W {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(self, buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(self)
+    }
+}
+
+/// Wraps a non-splittable `Transport` behind a shared `Mutex` so it can still
+/// produce a `(TransportRead, TransportWrite)` pair; used by backends (like
+/// `FakeTransport`) whose read and write sides aren't independent OS handles.
+/// Both halves serialize through the same lock, so this buys no concurrency —
+/// only the uniform split interface.
+pub(crate) struct SharedHalf<T>(Arc<Mutex<T>>);
+
+impl<T> SharedHalf<T> {
+    pub(crate) fn pair(transport: T) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>)
+    where
+        T: Transport + 'static,
+    {
+        let shared = Arc::new(Mutex::new(transport));
+        (Box::new(SharedHalf(shared.clone())), Box::new(SharedHalf(shared)))
+    }
+}
+
+impl<T: Transport + 'static> TransportRead for SharedHalf<T> {
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        self.0.lock().unwrap().read_line(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.0.lock().unwrap().read_exact(buf)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<T: Transport> TransportWrite for SharedHalf<T> {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.0.lock().unwrap().write_all(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.lock().unwrap().flush()
+    }
+}
+
 #[cfg(not(target_os = "windows"))]
-type Connection = std::os::unix::net::UnixStream;
+struct UnixSocketTransport {
+    reader: BufReader<std::os::unix::net::UnixStream>,
+    writer: std::os::unix::net::UnixStream,
+}
+
+#[cfg(not(target_os = "windows"))]
+impl Transport for UnixSocketTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut self.writer, buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(&mut self.writer)
+    }
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        BufRead::read_line(&mut self.reader, buf)
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        Read::read_exact(&mut self.reader, buf)
+    }
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        let this = *self;
+        (Box::new(this.reader), Box::new(this.writer))
+    }
+}
+
+struct WindowsPipeTransport {
+    reader: BufReader<std::fs::File>,
+    writer: std::fs::File,
+}
+
+#[cfg(target_os = "windows")]
+impl Transport for WindowsPipeTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut self.writer, buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(&mut self.writer)
+    }
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        BufRead::read_line(&mut self.reader, buf)
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        Read::read_exact(&mut self.reader, buf)
+    }
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        let this = *self;
+        (Box::new(this.reader), Box::new(this.writer))
+    }
+}
+
+/// Connects over TCP to a remote Aspire host daemon instead of a local socket.
+struct TcpTransport {
+    reader: BufReader<std::net::TcpStream>,
+    writer: std::net::TcpStream,
+}
+
+impl Transport for TcpTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut self.writer, buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(&mut self.writer)
+    }
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        BufRead::read_line(&mut self.reader, buf)
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        Read::read_exact(&mut self.reader, buf)
+    }
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        let this = *self;
+        (Box::new(this.reader), Box::new(this.writer))
+    }
+}
+
+/// Spawns the AppHost as a child process and speaks the length-prefixed
+/// JSON-RPC protocol over its stdin/stdout, so the host can run out-of-process
+/// without a pre-existing socket to connect to.
+struct StdioTransport {
+    // Kept alive for the transport's lifetime; killed on drop.
+    child: std::process::Child,
+    reader: BufReader<std::process::ChildStdout>,
+    writer: std::process::ChildStdin,
+}
+
+impl Transport for StdioTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut self.writer, buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(&mut self.writer)
+    }
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        BufRead::read_line(&mut self.reader, buf)
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        Read::read_exact(&mut self.reader, buf)
+    }
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        let this = *self;
+        (Box::new(this.reader), Box::new(StdioWriteHalf { child: this.child, writer: this.writer }))
+    }
+}
+
+/// Write half of a split `StdioTransport`: keeps the child process alive (and
+/// kills it on drop) since the reader half no longer holds a reference to it.
+struct StdioWriteHalf {
+    child: std::process::Child,
+    writer: std::process::ChildStdin,
+}
+
+impl TransportWrite for StdioWriteHalf {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut self.writer, buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(&mut self.writer)
+    }
+}
+
+impl Drop for StdioWriteHalf {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+    }
+}
+
+/// An SSH port forward (`ssh -L`) onto the AppHost's remote Unix socket,
+/// plus the local TCP connection riding over it. The child `ssh` process is
+/// kept alive for the tunnel's lifetime and killed on drop, same as
+/// `StdioTransport` keeps its spawned AppHost alive.
+struct SshTunnelTransport {
+    ssh_child: std::process::Child,
+    reader: BufReader<std::net::TcpStream>,
+    writer: std::net::TcpStream,
+}
+
+impl Transport for SshTunnelTransport {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut self.writer, buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(&mut self.writer)
+    }
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        BufRead::read_line(&mut self.reader, buf)
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        Read::read_exact(&mut self.reader, buf)
+    }
+    fn split(self: Box<Self>) -> (Box<dyn TransportRead>, Box<dyn TransportWrite>) {
+        let this = *self;
+        (Box::new(this.reader), Box::new(SshTunnelWriteHalf { ssh_child: this.ssh_child, writer: this.writer }))
+    }
+}
+
+/// Write half of a split `SshTunnelTransport`: keeps the `ssh` child alive
+/// (and kills it on drop) since the reader half no longer references it.
+struct SshTunnelWriteHalf {
+    ssh_child: std::process::Child,
+    writer: std::net::TcpStream,
+}
+
+impl TransportWrite for SshTunnelWriteHalf {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut self.writer, buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Write::flush(&mut self.writer)
+    }
+}
+
+impl Drop for SshTunnelWriteHalf {
+    fn drop(&mut self) {
+        let _ = self.ssh_child.kill();
+    }
+}
+
+/// Selects how an `AspireClient` reaches the AppHost, chosen once at
+/// construction time (`AspireClient::new`, `::tcp`, `::stdio`, `::ssh`).
+pub enum TransportKind {
+    /// Unix domain socket (or Windows named pipe) at this path.
+    Socket(String),
+    /// TCP connection to a remote host daemon, e.g. `"127.0.0.1:9182"`.
+    Tcp(String),
+    /// Spawn `command args...` and speak the protocol over its stdio.
+    Stdio { command: String, args: Vec<String> },
+    /// gRPC endpoint (e.g. `"http://127.0.0.1:9182"`), spoken over
+    /// `crate::grpc::GrpcTransport` instead of a raw socket. Only available
+    /// with the `grpc` feature.
+    #[cfg(feature = "grpc")]
+    Grpc(String),
+    /// An AppHost whose Unix socket only exists on a remote machine, reached
+    /// by shelling out to the system `ssh` binary to forward a local TCP
+    /// port onto that remote socket (OpenSSH 6.7+'s `-L port:socket_path`
+    /// forwarding), then connecting over TCP to the forwarded port. See
+    /// `AspireClient::ssh`.
+    Ssh(SshConfig),
+    /// Wraps `inner` in the encrypted, authenticated handshake `security.rs`
+    /// implements before any `Content-Length` frame is exchanged over it. See
+    /// `AspireClient::with_security`. Only available with the `security`
+    /// feature.
+    #[cfg(feature = "security")]
+    Secure {
+        inner: Box<TransportKind>,
+        security: crate::security::SecurityConfig,
+    },
+}
+
+/// How an SSH-tunneled connection authenticates to the jump host.
+#[derive(Debug, Clone)]
+pub enum SshAuth {
+    /// Password authentication, supplied to `ssh` via `sshpass -p`.
+    Password(String),
+    /// Path to a private key file, passed through as `ssh -i <path>`.
+    KeyFile(String),
+}
+
+/// Parameters for an SSH-tunneled `TransportKind::Ssh` connection.
+#[derive(Debug, Clone)]
+pub struct SshConfig {
+    pub host: String,
+    pub ssh_port: u16,
+    pub user: String,
+    pub auth: SshAuth,
+    /// Path of the AppHost's Unix socket on the remote machine.
+    pub remote_socket_path: String,
+    /// Local TCP port the tunnel is forwarded onto.
+    pub local_port: u16,
+}
+
+/// Controls `AspireClient::with_reconnect`'s automatic recovery from a
+/// broken connection: exponential backoff starting at `initial_backoff`,
+/// doubling on each failed attempt up to `max_backoff`, giving up after
+/// `max_attempts` attempts (`None` retries forever).
+#[derive(Debug, Clone)]
+pub struct ReconnectConfig {
+    pub initial_backoff: std::time::Duration,
+    pub max_backoff: std::time::Duration,
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff: std::time::Duration::from_millis(100),
+            max_backoff: std::time::Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+/// Configuration accepted by `AspireClient::connect_with`, for selecting a
+/// transport at connect time rather than at client-construction time (e.g.
+/// when the choice comes from parsed config rather than a literal call to
+/// `::tcp`/`::ssh`).
+pub enum TransportConfig {
+    Socket(String),
+    Tcp { host: String, port: u16 },
+    Ssh(SshConfig),
+    /// A gRPC endpoint (e.g. `"http://127.0.0.1:9182"`), connected the same
+    /// way `AspireClient::grpc` does — `ProstWireFormat` rather than the
+    /// JSON envelope the other variants use, since it's the pairing this
+    /// SDK ships gRPC with. Only available with the `grpc` feature.
+    #[cfg(feature = "grpc")]
+    Grpc(String),
+    /// Wraps `inner` in `security.rs`'s Noise handshake — see
+    /// `TransportKind::Secure`/`AspireClient::with_security`. Only available
+    /// with the `security` feature.
+    #[cfg(feature = "security")]
+    Secure {
+        inner: Box<TransportConfig>,
+        security: crate::security::SecurityConfig,
+    },
+}
+
+/// Parses a `REMOTE_APP_HOST_ENDPOINT`-style URL into the `TransportConfig`
+/// it names, so `connect()`/`connect_async()` can dispatch to the right
+/// transport from one variable instead of a different env var per kind:
+///
+/// - `unix:///path/to.sock` — the existing Unix-domain-socket transport.
+/// - `npipe://name` — a Windows named pipe, carried as the same
+///   `TransportConfig::Socket` (see `open_socket`'s `#[cfg(windows)]` half,
+///   which already treats its path argument as a pipe name on that platform).
+/// - `tcp://host:port` — a direct TCP connection to a remote AppHost daemon.
+///
+/// SSH tunneling has too many independent knobs (user, auth, remote path,
+/// local port) to fit one URL, so it's left to its own `REMOTE_APP_HOST_SSH_*`
+/// variables rather than folded in here.
+pub fn parse_endpoint(endpoint: &str) -> Result<TransportConfig, String> {
+    if let Some(path) = endpoint.strip_prefix("unix://") {
+        return Ok(TransportConfig::Socket(path.to_string()));
+    }
+    if let Some(name) = endpoint.strip_prefix("npipe://") {
+        return Ok(TransportConfig::Socket(name.to_string()));
+    }
+    if let Some(addr) = endpoint.strip_prefix("tcp://") {
+        let (host, port) = addr
+            .rsplit_once(':')
+            .ok_or_else(|| format!("REMOTE_APP_HOST_ENDPOINT `{}` is missing a port (expected tcp://host:port)", endpoint))?;
+        let port: u16 = port
+            .parse()
+            .map_err(|_| format!("REMOTE_APP_HOST_ENDPOINT `{}` has a non-numeric port", endpoint))?;
+        return Ok(TransportConfig::Tcp { host: host.to_string(), port });
+    }
+    Err(format!(
+        "REMOTE_APP_HOST_ENDPOINT `{}` has an unrecognized scheme (expected unix://, npipe://, or tcp://)",
+        endpoint
+    ))
+}
 
 /// Standard ATS error codes.
 pub mod ats_error_codes {
@@ -25,22 +446,6 @@ pub mod ats_error_codes {
     pub const INTERNAL_ERROR: &str = "INTERNAL_ERROR";
 }
 
-/// Error returned from capability invocations.
-#[derive(Debug, Clone)]
-pub struct CapabilityError {
-    pub code: String,
-    pub message: String,
-    pub capability: Option<String>,
-}
-
-impl std::fmt::Display for CapabilityError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
-    }
-}
-
-impl std::error::Error for CapabilityError {}
-
 /// A reference to a server-side object.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Handle {
@@ -78,6 +483,16 @@ pub fn is_marshalled_handle(value: &Value) -> bool {
     }
 }
 
+/// Recovers a boxed `AspireError` (e.g. `AspireError::Rpc` raised by
+/// `send_request`) without losing its variant, falling back to wrapping
+/// anything else as a generic `Transport` error.
+fn downcast_or_wrap(error: Box<dyn std::error::Error>) -> AspireError {
+    match error.downcast::<AspireError>() {
+        Ok(aspire_error) => *aspire_error,
+        Err(other) => AspireError::Transport(std::io::Error::new(std::io::ErrorKind::Other, other.to_string())),
+    }
+}
+
 /// Checks if a value is an ATS error.
 pub fn is_ats_error(value: &Value) -> bool {
     if let Value::Object(obj) = value {
@@ -94,6 +509,249 @@ lazy_static::lazy_static! {
     static ref HANDLE_WRAPPER_REGISTRY: RwLock<HashMap<String, HandleWrapperFactory>> = RwLock::new(HashMap::new());
     static ref CALLBACK_REGISTRY: Mutex<HashMap<String, Box<dyn Fn(Vec<Value>) -> Value + Send + Sync>>> = Mutex::new(HashMap::new());
     static ref CALLBACK_COUNTER: AtomicU64 = AtomicU64::new(0);
+    static ref SUBSCRIPTION_REGISTRY: Mutex<HashMap<String, std::sync::mpsc::Sender<Value>>> = Mutex::new(HashMap::new());
+    static ref SUBSCRIPTION_COUNTER: AtomicU64 = AtomicU64::new(0);
+    /// Handlers registered via `AspireClient::subscribe_event`, keyed by the
+    /// JSON-RPC `method` name a host-pushed notification carries -- distinct
+    /// from `SUBSCRIPTION_REGISTRY`, which is keyed by an opaque id minted
+    /// for one `watch_capability` invocation. An event name can have more
+    /// than one handler, so each entry is a `Vec` rather than a single slot;
+    /// the `u64` lets `unsubscribe` remove exactly the handler a given
+    /// `SubscriptionToken` was issued for without disturbing the others.
+    static ref EVENT_REGISTRY: Mutex<HashMap<String, Vec<(u64, Box<dyn Fn(Value) + Send + Sync>)>>> = Mutex::new(HashMap::new());
+    static ref EVENT_TOKEN_COUNTER: AtomicU64 = AtomicU64::new(0);
+    /// Cancellation tokens for in-flight `invokeCallback` dispatches, keyed by
+    /// the host's JSON-RPC request id for that call (see
+    /// `AspireClient::dispatch_callback_frame`). Entries are inserted only
+    /// when the call's args carry a `cancellationToken` id and removed once
+    /// the callback returns; a host-sent `cancelCallback` notification looks
+    /// the id up here and cancels it.
+    static ref CALLBACK_CANCELLATIONS: Mutex<HashMap<u64, Arc<CancellationToken>>> = Mutex::new(HashMap::new());
+}
+
+std::thread_local! {
+    /// Set for the duration of one callback invocation (see
+    /// `CallbackCancellationScope`) so the running closure can call
+    /// `current_callback_cancellation()` to check whether the host has asked
+    /// it to stop, without `register_callback`'s `Fn(Vec<Value>) -> Value`
+    /// signature having to carry a token through every call site.
+    static CURRENT_CALLBACK_CANCELLATION: std::cell::RefCell<Option<Arc<CancellationToken>>> = std::cell::RefCell::new(None);
+}
+
+/// Returns the `CancellationToken` the host attached to the callback
+/// currently running on this thread, if any. Only meaningful when called
+/// from inside a closure registered via `register_callback`/
+/// `register_async_callback` while it's actually being dispatched; outside
+/// that window (or when the host invoked it without a `cancellationToken`
+/// arg) this returns `None`.
+pub fn current_callback_cancellation() -> Option<Arc<CancellationToken>> {
+    CURRENT_CALLBACK_CANCELLATION.with(|cell| cell.borrow().clone())
+}
+
+/// RAII guard installing `token` as `current_callback_cancellation()`'s
+/// result for the lifetime of one callback dispatch, restoring whatever was
+/// there before (`None` in practice, since dispatches don't nest) on drop.
+struct CallbackCancellationScope;
+
+impl CallbackCancellationScope {
+    fn enter(token: Arc<CancellationToken>) -> Self {
+        CURRENT_CALLBACK_CANCELLATION.with(|cell| *cell.borrow_mut() = Some(token));
+        Self
+    }
+}
+
+impl Drop for CallbackCancellationScope {
+    fn drop(&mut self) {
+        CURRENT_CALLBACK_CANCELLATION.with(|cell| *cell.borrow_mut() = None);
+    }
+}
+
+/// Generates a subscription id and registers its channel sender so that
+/// host-pushed events tagged with that id are forwarded to the returned
+/// receiver rather than treated as a one-shot capability result.
+pub fn register_subscription() -> (String, std::sync::mpsc::Receiver<Value>) {
+    let id = format!(
+        "sub_{}_{}",
+        SUBSCRIPTION_COUNTER.fetch_add(1, Ordering::SeqCst),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+    );
+    let (tx, rx) = std::sync::mpsc::channel();
+    SUBSCRIPTION_REGISTRY.lock().unwrap().insert(id.clone(), tx);
+    (id, rx)
+}
+
+/// Unregisters a subscription, dropping its sender so the host can stop pushing.
+pub fn unregister_subscription(subscription_id: &str) -> bool {
+    SUBSCRIPTION_REGISTRY.lock().unwrap().remove(subscription_id).is_some()
+}
+
+/// Recognizes the terminal frame a streaming capability (resource logs,
+/// `watch`-style progress) sends to mark the stream as finished, as opposed
+/// to an ordinary payload the subscriber should keep consuming: a
+/// `{"$streamEnd": <null-or-error>}` object. Returns the inner value when
+/// `payload` is one.
+fn stream_end_payload(payload: &Value) -> Option<&Value> {
+    match payload {
+        Value::Object(obj) => obj.get("$streamEnd"),
+        _ => None,
+    }
+}
+
+/// Forwards a host-pushed event to the subscriber registered for `subscription_id`.
+/// Returns `false` if there is no such subscription (e.g. it was already dropped).
+///
+/// A `$streamEnd` frame unregisters the subscription instead of forwarding
+/// it, so the host can end a stream on its own (logs finished, `watch`
+/// reached a terminal state) rather than relying solely on the client
+/// dropping its `EventStream` to stop the flow. A non-null inner value means
+/// the stream ended with an error, logged here since `EventStream::next`
+/// has no channel back to the caller once the sender side is gone.
+fn dispatch_subscription_event(subscription_id: &str, payload: Value) -> bool {
+    if let Some(end) = stream_end_payload(&payload) {
+        if !end.is_null() {
+            eprintln!("[Rust ATS] stream `{}` ended with an error: {}", subscription_id, end);
+        }
+        SUBSCRIPTION_REGISTRY.lock().unwrap().remove(subscription_id);
+        return true;
+    }
+
+    let registry = SUBSCRIPTION_REGISTRY.lock().unwrap();
+    match registry.get(subscription_id) {
+        Some(sender) => sender.send(payload).is_ok(),
+        None => false,
+    }
+}
+
+/// Pushes a client-side-derived event (as opposed to one that arrived over
+/// the wire) onto a subscription's channel, for feeders like
+/// `watch_resource_state_stream`'s blocking-query thread that synthesize
+/// their own `EventStream` items rather than relaying a host-pushed frame.
+/// Returns `false` once the stream side has dropped the receiver.
+pub fn push_subscription_event(subscription_id: &str, payload: Value) -> bool {
+    dispatch_subscription_event(subscription_id, payload)
+}
+
+/// Delivers a host-pushed notification to the callback registered under
+/// `callback_id`, with no reply sent back over the wire. Used for push-style
+/// event subscriptions (e.g. `IDistributedApplicationEventing::subscribe`)
+/// where `invokeCallback`'s request/response round trip would make the host
+/// wait on a reply it doesn't need. Returns `false` if no callback is
+/// registered under that id (e.g. it already unsubscribed).
+fn dispatch_callback_notification(callback_id: &str, payload: Value) -> bool {
+    let registry = CALLBACK_REGISTRY.lock().unwrap();
+    match registry.get(callback_id) {
+        Some(callback) => {
+            callback(vec![payload]);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Fans out a host-pushed notification frame to whichever subscriber kind is
+/// registered under `subscription_id` — a channel sender (`EventStream`,
+/// `subscribe_stream`) or a callback (`subscribe`-style handlers). The frame
+/// itself (see `AspireClient::send_request`) carries no request id and
+/// expects no reply, so delivery here never blocks on, or races, an
+/// in-flight `invokeCallback` dispatch for the same id.
+fn dispatch_notification(subscription_id: &str, payload: Value) -> bool {
+    if dispatch_subscription_event(subscription_id, payload.clone()) {
+        return true;
+    }
+    dispatch_callback_notification(subscription_id, payload)
+}
+
+/// Opaque handle returned by `AspireClient::subscribe_event`, passed back to
+/// `AspireClient::unsubscribe` to remove that one handler. Unlike
+/// `CallbackGuard`, this is plain data rather than an RAII guard -- most
+/// event subscriptions (resource state changes, log lines) are meant to live
+/// for the process lifetime, so there's no `Drop` impl that unsubscribes
+/// implicitly on scope exit.
+pub struct SubscriptionToken {
+    event_name: String,
+    id: u64,
+}
+
+/// Registers `handler` under `event_name` in `EVENT_REGISTRY` and returns the
+/// token `AspireClient::unsubscribe` needs to remove it again.
+fn register_event_handler<F>(event_name: &str, handler: F) -> SubscriptionToken
+where
+    F: Fn(Value) + Send + Sync + 'static,
+{
+    let id = EVENT_TOKEN_COUNTER.fetch_add(1, Ordering::SeqCst);
+    EVENT_REGISTRY
+        .lock()
+        .unwrap()
+        .entry(event_name.to_string())
+        .or_insert_with(Vec::new)
+        .push((id, Box::new(handler)));
+    SubscriptionToken {
+        event_name: event_name.to_string(),
+        id,
+    }
+}
+
+/// Removes the single handler `token` was issued for, dropping the
+/// `event_name` entry entirely once its last handler is gone so a long-lived
+/// process doesn't accumulate empty `Vec`s for events nobody listens to
+/// anymore. Returns `false` if the token's handler was already removed.
+fn unregister_event_handler(token: &SubscriptionToken) -> bool {
+    let mut registry = EVENT_REGISTRY.lock().unwrap();
+    let Some(handlers) = registry.get_mut(&token.event_name) else { return false };
+    let before = handlers.len();
+    handlers.retain(|(id, _)| *id != token.id);
+    let removed = handlers.len() != before;
+    if handlers.is_empty() {
+        registry.remove(&token.event_name);
+    }
+    removed
+}
+
+/// Fans a host-pushed JSON-RPC notification (a `method`-bearing frame with no
+/// `id` -- distinct from an `invokeCallback` request, which expects a result
+/// back) out to every handler `AspireClient::subscribe_event` registered under
+/// `event_name`. Handlers run in registration order on the same thread that
+/// calls this, so two notifications for the same event are always delivered
+/// in the order `read_and_dispatch_one` read them off the wire; a slow
+/// handler does stall later events for that one event name, but (unlike
+/// `dispatch_callback_frame`, which hands `invokeCallback` off to its own
+/// thread) there's no reply the host is blocked waiting on, so this keeps
+/// things simple rather than paying for per-notification threads. Returns
+/// `false` if nothing is subscribed to `event_name`.
+fn dispatch_event(event_name: &str, payload: Value) -> bool {
+    let registry = EVENT_REGISTRY.lock().unwrap();
+    match registry.get(event_name) {
+        Some(handlers) if !handlers.is_empty() => {
+            for (_, handler) in handlers {
+                handler(payload.clone());
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Invokes the callback registered under `callback_id` directly, bypassing
+/// the wire entirely. Used by `test_support::FakeHost::invoke_callback` to
+/// drive callback-taking methods (`with_validator`, `with_cancellable_operation`)
+/// end-to-end without a live host. Returns `None` if no callback is
+/// registered under that id.
+#[cfg(feature = "test-support")]
+pub fn invoke_registered_callback(callback_id: &str, positional_args: Vec<Value>) -> Option<Value> {
+    let registry = CALLBACK_REGISTRY.lock().unwrap();
+    registry.get(callback_id).map(|callback| callback(positional_args))
+}
+
+/// Delivers a notification frame for `subscription_id` directly, bypassing
+/// the wire entirely. Used by `test_support::FakeHost::push_notification` to
+/// drive `subscribe`/`subscribe_stream` consumers end-to-end without a live
+/// host. Returns `false` if there is no live subscriber under that id.
+#[cfg(feature = "test-support")]
+pub fn push_test_notification(subscription_id: &str, payload: Value) -> bool {
+    dispatch_notification(subscription_id, payload)
 }
 
 /// Registers a handle wrapper factory for a type.
@@ -125,7 +783,7 @@ where
             .unwrap()
             .as_millis()
     );
-    
+
     let mut registry = CALLBACK_REGISTRY.lock().unwrap();
     registry.insert(id.clone(), Box::new(callback));
     id
@@ -137,6 +795,146 @@ pub fn unregister_callback(callback_id: &str) -> bool {
     registry.remove(callback_id).is_some()
 }
 
+/// Implemented by generated callback-context wrapper types (e.g.
+/// `ResourceUrlsCallbackContext`) so `register_context_callback` can rebuild
+/// one from the `Handle` a host callback invocation passes as its first
+/// positional argument.
+pub trait FromHandle {
+    fn from_handle(handle: Handle, client: Arc<AspireClient>) -> Self;
+}
+
+/// Registers a callback that receives a typed context wrapper (`T`) instead
+/// of the raw `Vec<Value>` `register_callback` hands every other callback.
+/// `args[0]` is expected to be the context's marshalled `Handle`, matching
+/// how the host invokes `with_urls_callback`-style capabilities; `T` is
+/// reconstructed from it via `FromHandle` before the closure runs, so the
+/// closure body can use the context's own typed accessors (`urls()`,
+/// `cancellation_token()`, ...) instead of hand-indexing positional args.
+pub fn register_context_callback<T, F>(client: Arc<AspireClient>, callback: F) -> String
+where
+    T: FromHandle,
+    F: Fn(&T) -> Value + Send + Sync + 'static,
+{
+    register_callback(move |args: Vec<Value>| {
+        let handle: Handle = args
+            .get(0)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_else(|| Handle::new(String::new(), String::new()));
+        let context = T::from_handle(handle, client.clone());
+        callback(&context)
+    })
+}
+
+/// A registered async callback, boxed the same way `futures`-style
+/// combinators erase a generic `Future` so heterogeneous closures can share
+/// one registry.
+#[cfg(feature = "tokio")]
+type AsyncCallback = Box<dyn Fn(Vec<Value>) -> std::pin::Pin<Box<dyn std::future::Future<Output = Value> + Send>> + Send + Sync>;
+
+// `lazy_static!` doesn't propagate a per-item `#[cfg]` onto the `impl Deref`
+// it generates for that item, so `ASYNC_CALLBACK_REGISTRY` can't share the
+// unconditional block above without leaking a reference to the
+// `tokio`-gated `AsyncCallback` alias into non-`tokio` builds; it gets its
+// own `cfg`-gated block instead.
+#[cfg(feature = "tokio")]
+lazy_static::lazy_static! {
+    static ref ASYNC_CALLBACK_REGISTRY: Mutex<HashMap<String, AsyncCallback>> = Mutex::new(HashMap::new());
+}
+
+/// Registers a callback whose body is an `async` block (or otherwise returns
+/// a `Future`) instead of resolving to a `Value` immediately, for
+/// `with_args_callback_async`/`with_command`-style handlers that need to
+/// genuinely `.await` work (another capability call, I/O, a timer) rather
+/// than blocking the dispatch thread `dispatch_callback_frame` spawned for
+/// this invocation. Gated behind the `tokio` feature since running the
+/// future to completion needs a runtime (see `invoke_callback`).
+#[cfg(feature = "tokio")]
+pub fn register_async_callback<F, Fut>(callback: F) -> String
+where
+    F: Fn(Vec<Value>) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Value> + Send + 'static,
+{
+    let id = format!(
+        "callback_{}_{}",
+        CALLBACK_COUNTER.fetch_add(1, Ordering::SeqCst),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+    );
+
+    let mut registry = ASYNC_CALLBACK_REGISTRY.lock().unwrap();
+    registry.insert(id.clone(), Box::new(move |args| Box::pin(callback(args))));
+    id
+}
+
+/// Unregisters an async callback by ID.
+#[cfg(feature = "tokio")]
+pub fn unregister_async_callback(callback_id: &str) -> bool {
+    let mut registry = ASYNC_CALLBACK_REGISTRY.lock().unwrap();
+    registry.remove(callback_id).is_some()
+}
+
+/// One host→client `invokeCallback` frame, as reported by
+/// `AspireClient::poll_for_callback`.
+#[derive(Debug, Clone)]
+pub struct CallbackInvocation {
+    pub callback_id: String,
+    pub args: Vec<Value>,
+}
+
+/// Owns a registered callback's lifetime. Dropping the guard removes the
+/// closure from the local registry and tells the host to release it via the
+/// `releaseCallback` capability, so builders that re-register callbacks on
+/// every reconfiguration (`with_validator`, `with_cancellable_operation`, …)
+/// don't leak one closure per call for the life of the process.
+///
+/// Call `leak()` to opt out for callbacks the host needs to keep permanently
+/// (e.g. a long-lived event handler) — this forgets the guard without
+/// releasing the callback.
+pub struct CallbackGuard {
+    callback_id: String,
+    client: Arc<AspireClient>,
+    released: bool,
+}
+
+impl CallbackGuard {
+    fn new(callback_id: String, client: Arc<AspireClient>) -> Self {
+        client.outstanding_callbacks.lock().unwrap().insert(callback_id.clone());
+        Self { callback_id, client, released: false }
+    }
+
+    /// Returns the id to pass to the host as the callback argument.
+    pub fn id(&self) -> &str {
+        &self.callback_id
+    }
+
+    /// Keeps the callback registered forever, skipping the `releaseCallback`
+    /// teardown this guard would otherwise perform on drop.
+    pub fn leak(mut self) -> String {
+        self.released = true;
+        self.client.outstanding_callbacks.lock().unwrap().remove(&self.callback_id);
+        self.callback_id.clone()
+    }
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        self.released = true;
+        unregister_callback(&self.callback_id);
+        #[cfg(feature = "tokio")]
+        unregister_async_callback(&self.callback_id);
+        self.client.outstanding_callbacks.lock().unwrap().remove(&self.callback_id);
+        self.client.callback_locks.lock().unwrap().remove(&self.callback_id);
+        let mut args = HashMap::new();
+        args.insert("callbackId".to_string(), Value::String(self.callback_id.clone()));
+        let _ = self.client.invoke_capability("Aspire.Hosting/releaseCallback", args);
+    }
+}
+
 /// Cancellation token for cooperative cancellation.
 pub struct CancellationToken {
     handle: Option<Handle>,
@@ -199,6 +997,20 @@ impl CancellationToken {
         let mut guard = self.callbacks.lock().unwrap();
         guard.push(Box::new(callback));
     }
+
+    /// Creates a cancellation source: a shared token paired with a
+    /// `CancellationTrigger` the Rust side owns. Calling `trigger.cancel()`
+    /// -- or simply dropping the trigger -- fires every callback registered
+    /// on the token (see `register`), including the one `register_cancellation`
+    /// installs to relay the signal to the host via `AspireClient::cancel_token`.
+    /// Use this instead of `new_local`/`new` when Rust code, not the host, is
+    /// the side that needs to trigger cancellation (a Ctrl-C handler, a
+    /// timeout future racing a `with_cancellable_operation`).
+    pub fn new_source() -> (Arc<CancellationToken>, CancellationTrigger) {
+        let token = Arc::new(CancellationToken::new_local());
+        let trigger = CancellationTrigger { token: token.clone() };
+        (token, trigger)
+    }
 }
 
 impl Default for CancellationToken {
@@ -207,6 +1019,28 @@ impl Default for CancellationToken {
     }
 }
 
+/// The Rust-side owner half of a `CancellationToken::new_source` pair.
+/// Cancels the paired token on an explicit `cancel()` call or, just as
+/// importantly, when dropped -- so a guard that goes out of scope (a
+/// `tokio::select!` branch losing the race, a Ctrl-C handler's scope
+/// ending) cancels the in-flight host operation instead of leaking it.
+pub struct CancellationTrigger {
+    token: Arc<CancellationToken>,
+}
+
+impl CancellationTrigger {
+    /// Cancels the paired token immediately.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+}
+
+impl Drop for CancellationTrigger {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
 /// Registers a cancellation token with the client.
 pub fn register_cancellation(token: &CancellationToken, client: Arc<AspireClient>) -> String {
     let id = format!(
@@ -220,50 +1054,717 @@ pub fn register_cancellation(token: &CancellationToken, client: Arc<AspireClient
             .unwrap()
             .as_nanos()
     );
-    
+
     let id_clone = id.clone();
     let client_clone = client;
     token.register(move || {
         let _ = client_clone.cancel_token(&id_clone);
     });
-    
+
     id
 }
 
+/// One entry of the backend's capability interface, as returned by
+/// `AspireClient::describe()`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CapabilityDescriptor {
+    pub name: String,
+    #[serde(default)]
+    pub required_args: Vec<String>,
+}
+
+/// Checks `args` against `capability_id`'s descriptor in `descriptors`,
+/// shared by `AspireClient::invoke_capability` (only run when
+/// `enable_strict_mode` is on) and the standalone `validate_args` generated
+/// wrappers can opt into ahead of a call they know is likely to be
+/// misconfigured (e.g. one built from caller-supplied data).
+fn validate_args(
+    descriptors: &HashMap<String, CapabilityDescriptor>,
+    capability_id: &str,
+    args: &HashMap<String, Value>,
+) -> Result<(), AspireError> {
+    let descriptor = descriptors
+        .get(capability_id)
+        .ok_or_else(|| AspireError::UnknownCapability {
+            name: capability_id.to_string(),
+        })?;
+    if let Some(missing) = descriptor
+        .required_args
+        .iter()
+        .find(|required| !args.contains_key(required.as_str()))
+    {
+        return Err(AspireError::ArgMismatch {
+            name: capability_id.to_string(),
+            message: format!("missing required argument `{}`", missing),
+        });
+    }
+    Ok(())
+}
+
+/// What one frame read off the connection turned out to be, returned by
+/// `AspireClient::read_and_dispatch_one` to whichever waiter happened to be
+/// acting as the connection's reader when it arrived.
+enum DispatchOutcome {
+    /// A JSON-RPC response tagged with a single request id.
+    Response(u64, Result<Value, AspireError>),
+    /// A batch reply (see `BatchBuilder::send`), keyed by the lowest request
+    /// id in the batch — the same `base_id` the sender computed.
+    Batch(u64, Vec<Value>),
+    /// A callback invocation or notification frame, already handed off
+    /// (`dispatch_callback_frame`/`dispatch_notification`) — nothing left for
+    /// the reader to do but go back and read the next frame.
+    Dispatched,
+}
+
+/// State shared by every thread waiting on a response from this connection.
+/// `has_reader` implements a leader/follower handoff: at most one thread
+/// blocks in `read_message` at a time (the "reader"); everyone else waits on
+/// `AspireClient::pending_cv` until either their own response shows up in
+/// `ready`/`ready_batches`, or the current reader finishes a frame and it's
+/// their turn to take over.
+#[derive(Default)]
+struct PendingState {
+    has_reader: bool,
+    ready: HashMap<u64, Result<Value, AspireError>>,
+    ready_batches: HashMap<u64, Vec<Value>>,
+    /// Set once `read_and_dispatch_one` hits a transport-level error *and*
+    /// reconnecting (see `ReconnectConfig`) either isn't configured or has
+    /// exhausted its attempts; every current and future waiter fails with a
+    /// fresh copy of it instead of blocking on a connection that's never
+    /// going to produce anything again. Cleared on a successful reconnect.
+    conn_error: Option<String>,
+    /// Every request that's been written to the connection but hasn't had
+    /// its reply observed yet, keyed by request id and holding the exact
+    /// JSON-RPC envelope that was sent — so a reconnect can replay it
+    /// verbatim rather than re-deriving it from whatever the caller's stack
+    /// frame still has in scope (which, for `invoke_capability_async`, is a
+    /// different thread entirely by the time a reconnect happens). Entries
+    /// are removed as soon as `drive_until` sees their response. Only covers
+    /// individual requests — `BatchBuilder::send` writes its array frame
+    /// directly rather than through `send_request_with_id`, so a batch
+    /// in flight during a reconnect is not replayed.
+    in_flight: HashMap<u64, Value>,
+}
+
+/// This client's own protocol version, sent to the AppHost during
+/// `connect()`'s `hello` handshake. Only the major component (the part
+/// before the first `.`) is checked for compatibility; a minor/patch bump
+/// on either side is assumed backwards compatible.
+const CLIENT_PROTOCOL_VERSION: &str = "1.0";
+
+/// Capability namespaces this generated SDK was built against. `connect()`
+/// fails fast if the AppHost doesn't report one of these, rather than
+/// letting the first call into a missing namespace fail deep inside
+/// `invoke_capability` with an opaque deserialization error.
+const CLIENT_CAPABILITY_NAMESPACES: &[&str] = &["Aspire.Hosting", "Aspire.Hosting.CodeGeneration.Rust.Tests"];
+
+/// Protocol-level feature names this generated SDK knows how to speak, sent
+/// alongside the version/namespaces in the `hello` handshake so the AppHost
+/// can tell which optional behaviors (as opposed to which capability
+/// namespaces) this client understands. Distinct from `describe()`'s
+/// per-capability `CapabilityDescriptor`s: these are transport-level, not
+/// tied to any one invokable capability name.
+const CLIENT_FEATURES: &[&str] = &["handles", "callbacks", "cancellation"];
+
+/// The AppHost's protocol version, capability namespaces, and advertised
+/// feature set, negotiated once during `connect()`'s `hello` handshake and
+/// cached for the client's lifetime. See `AspireClient::negotiated_protocol`.
+#[derive(Debug, Clone)]
+pub struct NegotiatedProtocol {
+    pub server_version: String,
+    pub server_namespaces: Vec<String>,
+    /// Feature names the AppHost echoed back as supported (e.g. `handles`,
+    /// `callbacks`, `cancellation`). An older AppHost that predates this
+    /// field in the `hello` reply negotiates an empty set rather than
+    /// failing, so callers should treat a missing feature as "assume not
+    /// supported" rather than "the handshake is broken".
+    pub server_features: std::collections::HashSet<String>,
+}
+
 /// Client for communicating with the AppHost server.
 pub struct AspireClient {
-    socket_path: String,
-    conn: Mutex<Option<Connection>>,
+    transport_kind: TransportKind,
+    reader: Mutex<Option<Box<dyn TransportRead>>>,
+    writer: Mutex<Option<Box<dyn TransportWrite>>>,
     next_id: AtomicU64,
     connected: AtomicBool,
     disconnect_callbacks: Mutex<Vec<Box<dyn Fn() + Send + Sync>>>,
+    /// Behind a `Mutex` (rather than a plain field) so `negotiate_wire_format`
+    /// can swap in `JsonWireFormat` after construction if the AppHost doesn't
+    /// accept the format the client proposed — `write_message`/`read_message`
+    /// always read whatever's parked here, so a fallback mid-`connect()`
+    /// doesn't need every prior caller of `with_wire_format` to know about it.
+    wire_format: Mutex<Box<dyn WireFormat>>,
+    pub(crate) kv_session_id: Mutex<Option<String>>,
+    sse_base_url: Option<String>,
+    outstanding_callbacks: Mutex<std::collections::HashSet<String>>,
+    strict_capabilities: Mutex<Option<HashMap<String, CapabilityDescriptor>>>,
+    /// Ordered, connection-scoped dispatch state (see `PendingState`) plus the
+    /// condvar threads wait on for either "it's my turn to read" or "my
+    /// response is ready".
+    pending: Mutex<PendingState>,
+    pending_cv: Condvar,
+    /// One `Mutex` per callback id, created lazily on first invocation, so
+    /// repeated invocations of the *same* callback never run concurrently or
+    /// out of order while distinct callback ids still run in parallel (see
+    /// `dispatch_callback_frame`).
+    callback_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    /// Mirrors every `invokeCallback` frame `dispatch_callback_frame` hands
+    /// off, so `poll_for_callback` can report them to an external event loop
+    /// without blocking. Unbounded: a caller that never polls just never
+    /// frees these, the same tradeoff `CALLBACK_REGISTRY` makes for
+    /// `register_callback` (vs. `register_callback_guarded`).
+    pending_callback_invocations: Mutex<VecDeque<CallbackInvocation>>,
+    /// Lets a background dispatch thread (which needs an owned `Arc` to write
+    /// a callback's response back) obtain one without every caller of
+    /// `invoke_capability` et al. having to hold `Arc<AspireClient>` just to
+    /// satisfy a receiver type. Always upgradable while any strong `Arc` to
+    /// this client is alive; see `AspireClient::arc`.
+    weak_self: std::sync::Weak<AspireClient>,
+    /// Merged into every `invoke_capability` call's request (see
+    /// `invoke_capability_with_metadata`) so cross-cutting concerns —
+    /// correlation id, auth/bearer token, trace context — ride along with
+    /// the capability args instead of being threaded through them by hand.
+    /// Set via `with_default_metadata`; a per-call metadata map passed to
+    /// `invoke_capability_with_metadata` overrides a key set here.
+    default_metadata: Mutex<HashMap<String, Value>>,
+    /// Lazily-populated cache of `describe()`'s result, independent of
+    /// `strict_capabilities` (whose presence also toggles validation on every
+    /// `invoke_capability` call). Backs `has_capability`/`validate_args` so a
+    /// caller can opt into feature-detection or one-off validation without
+    /// turning on strict mode for the whole client.
+    capability_cache: Mutex<Option<HashMap<String, CapabilityDescriptor>>>,
+    /// Set once by `connect()`'s `hello` handshake; see `negotiated_protocol`.
+    negotiated_protocol: Mutex<Option<NegotiatedProtocol>>,
+    /// `None` (the default) means a broken connection is fatal, same as
+    /// before this client supported reconnecting at all. Set via
+    /// `with_reconnect` to opt into `drive_until`'s automatic
+    /// re-`open_connection`-with-backoff recovery instead.
+    reconnect: Mutex<Option<ReconnectConfig>>,
+    /// Registered via `on_reconnect`; run (in registration order, same as
+    /// `disconnect_callbacks`) after a broken connection is replaced by a
+    /// freshly reconnected one and its in-flight requests have been resent,
+    /// so a `HandleWrapperBase`/`AspireList`/`AspireDict` can
+    /// `invalidate_handle` and re-resolve against the new session.
+    reconnect_callbacks: Mutex<Vec<Box<dyn Fn() + Send + Sync>>>,
 }
 
 impl AspireClient {
-    pub fn new(socket_path: &str) -> Self {
-        Self {
-            socket_path: socket_path.to_string(),
-            conn: Mutex::new(None),
+    pub fn new(socket_path: &str) -> Arc<Self> {
+        Self::with_wire_format(socket_path, Box::new(JsonWireFormat))
+    }
+
+    /// Creates a client that encodes messages with a non-default `WireFormat`
+    /// (e.g. MessagePack or bincode) instead of plain JSON. The chosen format's
+    /// name is sent in the connect handshake so the host can agree on it.
+    pub fn with_wire_format(socket_path: &str, wire_format: Box<dyn WireFormat>) -> Arc<Self> {
+        Self::with_transport_kind(TransportKind::Socket(socket_path.to_string()), wire_format)
+    }
+
+    /// Creates a client that reaches the AppHost over TCP instead of a local
+    /// socket, for attaching to a remote host daemon.
+    pub fn tcp(addr: &str) -> Arc<Self> {
+        Self::with_transport_kind(TransportKind::Tcp(addr.to_string()), Box::new(JsonWireFormat))
+    }
+
+    /// Creates a client that reaches an AppHost whose Unix socket only
+    /// exists on a remote machine, by tunneling through SSH instead of
+    /// requiring the socket to be exposed over plain TCP. See `SshConfig`.
+    pub fn ssh(config: SshConfig) -> Arc<Self> {
+        Self::with_transport_kind(TransportKind::Ssh(config), Box::new(JsonWireFormat))
+    }
+
+    /// Creates a client that reaches the AppHost the same way `inner` would,
+    /// but wraps the connection in `security.rs`'s encrypted, authenticated
+    /// Noise handshake before any `Content-Length` frame crosses it — for an
+    /// endpoint (a shared-host TCP port, a socket other local users can also
+    /// open) that plaintext JSON-RPC shouldn't be sent over unprotected.
+    /// `connect()` fails closed if the AppHost doesn't complete the
+    /// handshake with the same `security.psk`, rather than falling back to
+    /// the unencrypted path. Only available with the `security` feature.
+    #[cfg(feature = "security")]
+    pub fn with_security(inner: TransportKind, security: crate::security::SecurityConfig) -> Arc<Self> {
+        Self::with_transport_kind(
+            TransportKind::Secure { inner: Box::new(inner), security },
+            Box::new(JsonWireFormat),
+        )
+    }
+
+    /// Creates a client that reaches the AppHost over the same local socket
+    /// as `new`, but encodes the envelope with `ProstWireFormat` instead of
+    /// JSON text — the binary-encoding win without needing the `grpc`
+    /// transport or its multiplexed stream. The host must negotiate the
+    /// `prost` wire format during `connect()`'s handshake.
+    #[cfg(feature = "protobuf")]
+    pub fn protobuf(socket_path: &str) -> Arc<Self> {
+        Self::with_wire_format(socket_path, Box::new(crate::wire_format::ProstWireFormat))
+    }
+
+    /// Builds and connects a client from a `TransportConfig` chosen at
+    /// connect time (e.g. parsed from config/env vars) rather than via a
+    /// specific constructor like `::tcp`/`::ssh`.
+    pub fn connect_with(config: TransportConfig) -> Result<Arc<Self>, Box<dyn std::error::Error>> {
+        #[cfg(feature = "grpc")]
+        if let TransportConfig::Grpc(endpoint) = config {
+            let client = Self::grpc(&endpoint);
+            client.connect()?;
+            return Ok(client);
+        }
+
+        let kind = Self::transport_kind_from_config(config);
+        let client = Self::with_transport_kind(kind, Box::new(JsonWireFormat));
+        client.connect()?;
+        Ok(client)
+    }
+
+    fn transport_kind_from_config(config: TransportConfig) -> TransportKind {
+        match config {
+            TransportConfig::Socket(path) => TransportKind::Socket(path),
+            TransportConfig::Tcp { host, port } => TransportKind::Tcp(format!("{}:{}", host, port)),
+            TransportConfig::Ssh(ssh_config) => TransportKind::Ssh(ssh_config),
+            #[cfg(feature = "grpc")]
+            TransportConfig::Grpc(_) => unreachable!("handled above"),
+            #[cfg(feature = "security")]
+            TransportConfig::Secure { inner, security } => TransportKind::Secure {
+                inner: Box::new(Self::transport_kind_from_config(*inner)),
+                security,
+            },
+        }
+    }
+
+    /// Creates a client that reaches the AppHost over gRPC instead of a raw
+    /// socket, encoding the envelope with `ProstWireFormat` by default (pair
+    /// with `with_wire_format`-style construction if a different format is
+    /// ever needed — gRPC's framing doesn't require it to be Prost, but that's
+    /// the pairing this SDK ships).
+    #[cfg(feature = "grpc")]
+    pub fn grpc(endpoint: &str) -> Arc<Self> {
+        Self::grpc_with_wire_format(endpoint, Box::new(crate::wire_format::ProstWireFormat))
+    }
+
+    /// Creates a client that reaches the AppHost over gRPC with a caller-chosen
+    /// `WireFormat` instead of the default `ProstWireFormat` pairing — e.g.
+    /// `MessagePackWireFormat` if the host side doesn't speak protobuf's
+    /// `google.protobuf.Value` mapping but the deployment still wants gRPC's
+    /// multiplexed duplex stream for callback/notification traffic.
+    #[cfg(feature = "grpc")]
+    pub fn grpc_with_wire_format(endpoint: &str, wire_format: Box<dyn WireFormat>) -> Arc<Self> {
+        Self::with_transport_kind(TransportKind::Grpc(endpoint.to_string()), wire_format)
+    }
+
+    /// Test-only constructor that wires an already-open `Transport` straight
+    /// into the client, skipping `connect()`'s socket dial and
+    /// `negotiateWireFormat` handshake entirely. Used by
+    /// `test_support::fake_client` to back a real `AspireClient` with an
+    /// in-process `FakeHost` instead of a live .NET AppHost, so the same
+    /// generated wrappers run unchanged against it.
+    #[cfg(feature = "test-support")]
+    pub fn with_transport(transport: Box<dyn Transport>, wire_format: Box<dyn WireFormat>) -> Arc<Self> {
+        let client = Self::with_transport_kind(TransportKind::Socket(String::new()), wire_format);
+        let (reader, writer) = transport.split();
+        *client.reader.lock().unwrap() = Some(reader);
+        *client.writer.lock().unwrap() = Some(writer);
+        client.connected.store(true, Ordering::SeqCst);
+        client
+    }
+
+    /// Creates a client that spawns `command args...` and speaks the
+    /// protocol over its stdio, for running the AppHost out-of-process
+    /// without a pre-existing socket to connect to.
+    pub fn stdio(command: &str, args: &[&str]) -> Arc<Self> {
+        Self::with_transport_kind(
+            TransportKind::Stdio {
+                command: command.to_string(),
+                args: args.iter().map(|s| s.to_string()).collect(),
+            },
+            Box::new(JsonWireFormat),
+        )
+    }
+
+    fn with_transport_kind(transport_kind: TransportKind, wire_format: Box<dyn WireFormat>) -> Arc<Self> {
+        Arc::new_cyclic(|weak_self| Self {
+            transport_kind,
+            reader: Mutex::new(None),
+            writer: Mutex::new(None),
             next_id: AtomicU64::new(1),
             connected: AtomicBool::new(false),
             disconnect_callbacks: Mutex::new(Vec::new()),
+            wire_format: Mutex::new(wire_format),
+            kv_session_id: Mutex::new(None),
+            sse_base_url: None,
+            outstanding_callbacks: Mutex::new(std::collections::HashSet::new()),
+            strict_capabilities: Mutex::new(None),
+            pending: Mutex::new(PendingState::default()),
+            pending_cv: Condvar::new(),
+            callback_locks: Mutex::new(HashMap::new()),
+            pending_callback_invocations: Mutex::new(VecDeque::new()),
+            weak_self: weak_self.clone(),
+            default_metadata: Mutex::new(HashMap::new()),
+            capability_cache: Mutex::new(None),
+            negotiated_protocol: Mutex::new(None),
+            reconnect: Mutex::new(None),
+            reconnect_callbacks: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Merges `metadata` into the map attached to every subsequent
+    /// `invoke_capability` call on this client (correlation id, auth/bearer
+    /// token, trace context, …), so a whole fluent chain of resource calls
+    /// can be tied to one trace without passing it as an ordinary capability
+    /// argument. Chainable like the generated `with_*` builders; call again
+    /// to add more keys, or pass the same key to overwrite it.
+    pub fn with_default_metadata(self: Arc<Self>, metadata: HashMap<String, Value>) -> Arc<Self> {
+        self.default_metadata.lock().unwrap().extend(metadata);
+        self
+    }
+
+    /// Upgrades `weak_self` to a strong `Arc`, for handing an owned client
+    /// reference to a spawned dispatch thread. Returns `None` only once the
+    /// last external `Arc<AspireClient>` has already been dropped (i.e. the
+    /// client itself is mid-teardown), in which case there's no one left to
+    /// deliver a callback response to anyway.
+    fn arc(&self) -> Option<Arc<Self>> {
+        self.weak_self.upgrade()
+    }
+
+    /// Fetches the backend's supported capability names and parameter
+    /// schemas via the `org.aspire.GetInterface` introspection capability.
+    pub fn describe(&self) -> Result<Vec<CapabilityDescriptor>, AspireError> {
+        let result = self.invoke_capability("org.aspire.GetInterface", HashMap::new())?;
+        serde_json::from_value(result).map_err(AspireError::from)
+    }
+
+    /// Convenience over `describe()` for callers that just want the host's
+    /// capability names (e.g. to render a menu or drive a completion list)
+    /// without the per-entry parameter schema.
+    pub fn list_capabilities(&self) -> Result<Vec<String>, AspireError> {
+        Ok(self.cached_capabilities()?.into_keys().collect())
+    }
+
+    /// Looks up one capability's descriptor by id, `None` if the connected
+    /// host doesn't expose it. Like `has_capability` but returns the
+    /// parameter schema instead of a bare bool, for callers that want to
+    /// inspect `required_args` directly rather than just detect presence.
+    pub fn describe_capability(&self, capability_id: &str) -> Result<Option<CapabilityDescriptor>, AspireError> {
+        Ok(self.cached_capabilities()?.get(capability_id).cloned())
+    }
+
+    /// Enables strict mode: calls `describe()` once and caches the result,
+    /// after which every `invoke_capability` call is validated against it
+    /// before being sent — an unknown capability name or a missing required
+    /// argument fails locally with `UnknownCapability`/`ArgMismatch` instead
+    /// of an opaque error from the host.
+    pub fn enable_strict_mode(&self) -> Result<(), AspireError> {
+        let by_name = self
+            .describe()?
+            .into_iter()
+            .map(|d| (d.name.clone(), d))
+            .collect();
+        *self.strict_capabilities.lock().unwrap() = Some(by_name);
+        Ok(())
+    }
+
+    /// Returns `describe()`'s result keyed by capability name, calling
+    /// `describe()` only once per client and reusing the cached map on every
+    /// later call (see `capability_cache`).
+    fn cached_capabilities(&self) -> Result<HashMap<String, CapabilityDescriptor>, AspireError> {
+        if let Some(cached) = self.capability_cache.lock().unwrap().as_ref() {
+            return Ok(cached.clone());
+        }
+        let by_name: HashMap<String, CapabilityDescriptor> =
+            self.describe()?.into_iter().map(|d| (d.name.clone(), d)).collect();
+        *self.capability_cache.lock().unwrap() = Some(by_name.clone());
+        Ok(by_name)
+    }
+
+    /// Feature-detection: does the connected host support `capability_id`?
+    /// Useful for checking a capability that's only present on newer host
+    /// versions (e.g. `asHttp2Service`) before calling it, rather than
+    /// discovering the gap from a `CapabilityNotFound` error.
+    pub fn has_capability(&self, capability_id: &str) -> Result<bool, AspireError> {
+        Ok(self.cached_capabilities()?.contains_key(capability_id))
+    }
+
+    /// Returns the protocol version and capability namespaces the AppHost
+    /// reported during `connect()`'s `hello` handshake, or `None` if
+    /// `connect()` hasn't run yet. Generated wrappers can consult this to
+    /// conditionally enable a feature that depends on a namespace the
+    /// connected host might not have.
+    pub fn negotiated_protocol(&self) -> Option<NegotiatedProtocol> {
+        self.negotiated_protocol.lock().unwrap().clone()
+    }
+
+    /// Returns the feature names the AppHost advertised in its `hello` reply
+    /// (see `NegotiatedProtocol::server_features`), or an empty set if
+    /// `connect()` hasn't run yet or the connected AppHost predates
+    /// feature advertisement. Shorthand for
+    /// `negotiated_protocol().map(|p| p.server_features).unwrap_or_default()`
+    /// so call sites gating on one feature (e.g. `"cancellation"`) don't
+    /// need to unwrap the `Option` themselves.
+    pub fn server_capabilities(&self) -> std::collections::HashSet<String> {
+        self.negotiated_protocol.lock().unwrap().as_ref().map(|p| p.server_features.clone()).unwrap_or_default()
+    }
+
+    /// Returns the raw file descriptor behind this connection, for embedding
+    /// Aspire callback servicing into a caller-owned `select!`/epoll loop
+    /// alongside other I/O instead of dedicating `spawn_background_reader`'s
+    /// thread to it. Only a socket-backed transport (Unix socket, TCP, and
+    /// the SSH tunnel's local TCP forward) has one; the stdio subprocess and
+    /// gRPC transports return `None` since there's no single fd that reading
+    /// a complete frame from them reduces to. Returns `None` before
+    /// `connect()` as well.
+    #[cfg(unix)]
+    pub fn as_raw_fd(&self) -> Option<std::os::unix::io::RawFd> {
+        use std::os::unix::io::AsRawFd;
+        let reader = self.reader.lock().unwrap();
+        let reader = reader.as_ref()?;
+        if let Some(unix) = reader.as_any().downcast_ref::<BufReader<std::os::unix::net::UnixStream>>() {
+            return Some(unix.get_ref().as_raw_fd());
         }
+        if let Some(tcp) = reader.as_any().downcast_ref::<BufReader<std::net::TcpStream>>() {
+            return Some(tcp.get_ref().as_raw_fd());
+        }
+        None
+    }
+
+    /// Pops the oldest callback invocation the background reader has
+    /// received and already started dispatching (see `dispatch_callback_frame`),
+    /// without blocking. Pairs with `as_raw_fd`: a caller driving its own
+    /// event loop can wake on the fd becoming readable, then drain
+    /// `poll_for_callback` for visibility into what just arrived, instead of
+    /// only finding out indirectly once `register_callback`'s closure runs.
+    /// The invocation's closure still runs on `dispatch_callback_frame`'s own
+    /// thread either way — this does not hand the caller control over
+    /// dispatch, only a non-blocking window into it.
+    pub fn poll_for_callback(&self) -> Option<CallbackInvocation> {
+        self.pending_callback_invocations.lock().unwrap().pop_front()
+    }
+
+    /// Validates `args` against `capability_id`'s cached descriptor without
+    /// sending the call — an unknown capability name or a missing required
+    /// argument is reported locally as `UnknownCapability`/`ArgMismatch`
+    /// instead of round-tripping to the host to find out. Generated wrappers
+    /// (or callers building args dynamically) can opt into this per-call
+    /// without `enable_strict_mode` turning validation on for every call.
+    pub fn validate_args(&self, capability_id: &str, args: &HashMap<String, Value>) -> Result<(), AspireError> {
+        validate_args(&self.cached_capabilities()?, capability_id, args)
+    }
+
+    /// Registers `callback` and returns a `CallbackGuard` that releases it
+    /// (both locally and on the host) when dropped, instead of leaking it for
+    /// the process lifetime the way the plain `register_callback` free
+    /// function does.
+    pub fn register_callback_guarded<F>(self: &Arc<Self>, callback: F) -> CallbackGuard
+    where
+        F: Fn(Vec<Value>) -> Value + Send + Sync + 'static,
+    {
+        let callback_id = register_callback(callback);
+        CallbackGuard::new(callback_id, self.clone())
+    }
+
+    /// Like `register_callback_guarded`, but `callback` receives the
+    /// invocation's `CancellationToken` as an explicit second argument
+    /// instead of having to call `current_callback_cancellation()` inside
+    /// the closure body. Used by `with_cancellable_operation` so a long-
+    /// running operation can poll or `.wait()` on the token it was handed
+    /// rather than reaching into thread-local dispatch state. The token is
+    /// freshly created (and never cancelled) for an invocation the host made
+    /// without a `cancellationToken` id.
+    pub fn register_cancellable_callback<F>(self: &Arc<Self>, callback: F) -> CallbackGuard
+    where
+        F: Fn(Vec<Value>, Arc<CancellationToken>) -> Value + Send + Sync + 'static,
+    {
+        let wrapped = move |args: Vec<Value>| {
+            let token = current_callback_cancellation().unwrap_or_else(|| Arc::new(CancellationToken::new_local()));
+            callback(args, token)
+        };
+        self.register_callback_guarded(wrapped)
+    }
+
+    /// Like `register_callback_guarded`, but for an async callback (see
+    /// `register_async_callback`) — the returned guard releases it from
+    /// `ASYNC_CALLBACK_REGISTRY` on drop instead of `CALLBACK_REGISTRY`.
+    /// Gated behind the `tokio` feature, matching `register_async_callback`.
+    #[cfg(feature = "tokio")]
+    pub fn register_async_callback_guarded<F, Fut>(self: &Arc<Self>, callback: F) -> CallbackGuard
+    where
+        F: Fn(Vec<Value>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Value> + Send + 'static,
+    {
+        let callback_id = register_async_callback(callback);
+        CallbackGuard::new(callback_id, self.clone())
+    }
+
+    /// Points this client at the AppHost's Server-Sent-Events endpoint, e.g.
+    /// `http://localhost:18889`, enabling `event_stream` for host-push data
+    /// (resource logs, status transitions) that the request/response
+    /// transport cannot carry.
+    pub fn with_sse_endpoint(mut self, base_url: &str) -> Self {
+        self.sse_base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Opens a long-lived SSE connection for `capability_id` and returns an
+    /// iterator of decoded JSON events, for streaming data like live log
+    /// tailing where a one-shot `invoke_capability` reply doesn't fit.
+    pub fn event_stream(
+        &self,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<crate::sse::SseStream, AspireError> {
+        let base_url = self.sse_base_url.as_deref().ok_or_else(|| AspireError::CapabilityNotFound {
+            name: format!("{} (no SSE endpoint configured; call with_sse_endpoint first)", capability_id),
+            server_version: None,
+        })?;
+        crate::sse::SseStream::connect(base_url, capability_id, &Value::Object(args.into_iter().collect()))
     }
 
     /// Connects to the AppHost server.
+    ///
+    /// Splits the opened `Transport` into independent read/write halves
+    /// (`Transport::split`) before storing them, so a thread blocked reading
+    /// the next host frame never holds up another thread writing a request or
+    /// a callback response — see `read_and_dispatch_one` for how frames are
+    /// then fanned out in receive order.
     pub fn connect(&self) -> Result<(), Box<dyn std::error::Error>> {
         if self.connected.load(Ordering::SeqCst) {
             return Ok(());
         }
 
-        let conn = open_connection(&self.socket_path)?;
-        *self.conn.lock().unwrap() = Some(conn);
+        let conn = open_connection(&self.transport_kind)?;
+        let (reader, writer) = conn.split();
+        *self.reader.lock().unwrap() = Some(reader);
+        *self.writer.lock().unwrap() = Some(writer);
         self.connected.store(true, Ordering::SeqCst);
-        
+
         eprintln!("[Rust ATS] Connected to AppHost server");
+        self.negotiate_wire_format()?;
+        self.negotiate_protocol()?;
+
+        if let Some(client) = self.arc() {
+            client.spawn_background_reader();
+        }
+        Ok(())
+    }
+
+    /// Async variant of `connect`, gated behind the `tokio` feature. The
+    /// handshake itself is still the blocking `connect` above — opening a
+    /// socket and exchanging `negotiateWireFormat`/`hello` is cheap and
+    /// already off the reactor once `spawn_background_reader` takes over —
+    /// but running it via `std::thread::spawn` keeps it from blocking
+    /// whichever tokio worker thread an async caller's `connect_async().await`
+    /// happens to run on, the same tradeoff `invoke_capability_async` makes.
+    #[cfg(feature = "tokio")]
+    pub async fn connect_async(self: &Arc<Self>) -> Result<(), AspireError> {
+        let client = self.clone();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        std::thread::spawn(move || {
+            let result = client.connect().map_err(downcast_or_wrap);
+            let _ = tx.send(result);
+        });
+        rx.await.unwrap_or(Err(AspireError::Canceled))
+    }
+
+    /// Proposes this client's current wire format to the AppHost and falls
+    /// back to `JsonWireFormat` if it says no. A client constructed via
+    /// `with_wire_format(MessagePackWireFormat)` against an AppHost that
+    /// predates msgpack support (or was built without the matching codec)
+    /// still connects successfully instead of silently sending frames the
+    /// host can't decode — JSON is the one format every AppHost speaking
+    /// this protocol is guaranteed to understand, which is why it's the
+    /// universal fallback rather than some other non-default format.
+    fn negotiate_wire_format(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let proposed = self.wire_format.lock().unwrap().name();
+        let reply = self.send_request("negotiateWireFormat", json!([proposed]))?;
+        let accepted = reply.get("accepted").and_then(|v| v.as_bool()).unwrap_or(true);
+        if !accepted && proposed != "json" {
+            eprintln!(
+                "[Rust ATS] AppHost does not support wire format `{}`; falling back to json",
+                proposed
+            );
+            *self.wire_format.lock().unwrap() = Box::new(JsonWireFormat);
+        }
+        Ok(())
+    }
+
+    /// Sends the `hello` handshake right after `negotiateWireFormat`: this
+    /// client's protocol version and the capability namespaces it was
+    /// generated against, so a Rust client built against one AppHost surface
+    /// fails here with a clear message instead of deep inside the first
+    /// mismatched `invoke_capability` call. Fails `connect()` if the major
+    /// protocol versions disagree, or if the AppHost doesn't report a
+    /// namespace this client requires.
+    fn negotiate_protocol(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let reply = self.send_request(
+            "hello",
+            json!({
+                "version": CLIENT_PROTOCOL_VERSION,
+                "namespaces": CLIENT_CAPABILITY_NAMESPACES,
+                "features": CLIENT_FEATURES,
+            }),
+        )?;
+        let server_version = reply.get("version").and_then(|v| v.as_str()).unwrap_or("0.0").to_string();
+        let server_namespaces: Vec<String> = reply
+            .get("namespaces")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let server_features: std::collections::HashSet<String> = reply
+            .get("features")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+
+        let client_major = CLIENT_PROTOCOL_VERSION.split('.').next().unwrap_or(CLIENT_PROTOCOL_VERSION);
+        let server_major = server_version.split('.').next().unwrap_or(server_version.as_str());
+        if client_major != server_major {
+            return Err(format!(
+                "protocol version mismatch: this client speaks {} but the AppHost speaks {} (incompatible major version)",
+                CLIENT_PROTOCOL_VERSION, server_version
+            )
+            .into());
+        }
+        for namespace in CLIENT_CAPABILITY_NAMESPACES {
+            if !server_namespaces.iter().any(|s| s == namespace) {
+                return Err(format!(
+                    "AppHost at protocol version {} is missing required capability namespace: {}",
+                    server_version, namespace
+                )
+                .into());
+            }
+        }
+
+        let server_minor = server_version.split('.').nth(1).unwrap_or("0");
+        let client_minor = CLIENT_PROTOCOL_VERSION.split('.').nth(1).unwrap_or("0");
+        if server_minor != client_minor {
+            eprintln!(
+                "[Rust ATS] protocol minor version differs (client {}, server {}); proceeding, but some newer capabilities may be unavailable",
+                CLIENT_PROTOCOL_VERSION, server_version
+            );
+        }
+
+        *self.negotiated_protocol.lock().unwrap() = Some(NegotiatedProtocol {
+            server_version,
+            server_namespaces,
+            server_features,
+        });
         Ok(())
     }
 
+    /// Keeps host-pushed frames (callback invocations, subscription
+    /// notifications) flowing on an otherwise idle connection. Without this,
+    /// nothing reads the socket while no `invoke_capability`/`watch_*` call
+    /// is outstanding to take the reader's turn (see `drive_until`), so a
+    /// callback on a long-lived resource (`with_validator`, `with_command`'s
+    /// `execute_command`) would sit undelivered until the caller happened to
+    /// make some other call. Spawned once by `connect()`; exits once
+    /// `read_and_dispatch_one` reports a connection error (the host closed
+    /// the stream, or `disconnect()` cleared the reader/writer out from
+    /// under it).
+    fn spawn_background_reader(self: Arc<Self>) {
+        std::thread::spawn(move || while self.drive_until(None, |_| None::<()>).is_ok() {});
+    }
+
     /// Registers a callback for disconnection.
     pub fn on_disconnect<F>(&self, callback: F)
     where
@@ -273,20 +1774,289 @@ impl AspireClient {
         callbacks.push(Box::new(callback));
     }
 
+    /// Opts this client into automatically recovering from a broken
+    /// connection instead of failing every call from then on: `drive_until`
+    /// re-`open_connection`s with exponential backoff (`config.initial_backoff`,
+    /// doubling up to `config.max_backoff`, giving up after
+    /// `config.max_attempts` if set), re-runs the `negotiateWireFormat`/`hello`
+    /// handshake, then replays every request that was written but hadn't
+    /// gotten a reply yet (see `PendingState::in_flight`) before letting
+    /// `on_reconnect` callbacks run. Without this, a broken `Connection`
+    /// behaves as it always has: every waiter fails and stays failed.
+    pub fn with_reconnect(self: Arc<Self>, config: ReconnectConfig) -> Arc<Self> {
+        *self.reconnect.lock().unwrap() = Some(config);
+        self
+    }
+
+    /// Registers a callback to run after a successful reconnect (see
+    /// `with_reconnect`), once in-flight requests have been replayed but
+    /// before their replies are observed. Server-side `Handle`s may have
+    /// been invalidated by whatever caused the AppHost to restart, so a
+    /// `HandleWrapperBase`/`AspireList`/`AspireDict` an application is
+    /// holding onto should use this to call `invalidate_handle` and force
+    /// its next use to re-resolve.
+    pub fn on_reconnect<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.reconnect_callbacks.lock().unwrap().push(Box::new(callback));
+    }
+
+    /// Called from `drive_until` once `read_and_dispatch_one` reports a
+    /// broken connection. Returns `true` if reconnecting succeeded (in which
+    /// case `drive_until` should loop back around and keep waiting — the
+    /// replayed requests' replies will show up the same way any other
+    /// response does) or `false` if reconnecting isn't configured or its
+    /// attempts were exhausted, in which case the original error should be
+    /// recorded as `conn_error` as before.
+    fn try_reconnect(&self, original_error: &AspireError) -> bool {
+        let Some(config) = self.reconnect.lock().unwrap().clone() else {
+            return false;
+        };
+        let Some(client) = self.arc() else {
+            return false;
+        };
+
+        self.connected.store(false, Ordering::SeqCst);
+        *self.reader.lock().unwrap() = None;
+        *self.writer.lock().unwrap() = None;
+
+        let mut backoff = config.initial_backoff;
+        let mut attempt: u32 = 0;
+        loop {
+            if let Some(max_attempts) = config.max_attempts {
+                if attempt >= max_attempts {
+                    eprintln!(
+                        "[Rust ATS] Giving up reconnecting after {} attempts (original error: {})",
+                        attempt, original_error
+                    );
+                    return false;
+                }
+            }
+            attempt += 1;
+
+            eprintln!("[Rust ATS] Connection broken ({}), reconnect attempt {} in {:?}", original_error, attempt, backoff);
+            std::thread::sleep(backoff);
+            backoff = std::cmp::min(backoff * 2, config.max_backoff);
+
+            match client.reestablish_connection() {
+                Ok(()) => break,
+                Err(e) => {
+                    eprintln!("[Rust ATS] Reconnect attempt {} failed: {}", attempt, e);
+                    continue;
+                }
+            }
+        }
+
+        eprintln!("[Rust ATS] Reconnected to AppHost");
+
+        let in_flight: Vec<Value> = self.pending.lock().unwrap().in_flight.values().cloned().collect();
+        for message in in_flight {
+            if let Err(e) = self.write_message(&message) {
+                eprintln!("[Rust ATS] Failed to replay in-flight request after reconnect: {}", e);
+            }
+        }
+
+        for callback in self.reconnect_callbacks.lock().unwrap().iter() {
+            callback();
+        }
+
+        true
+    }
+
+    /// Does the `open_connection` + handshake half of `connect()` without
+    /// `connect()`'s early-return-if-already-connected guard, so
+    /// `try_reconnect` can call it unconditionally after a broken connection
+    /// has already been torn down.
+    fn reestablish_connection(self: &Arc<Self>) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = open_connection(&self.transport_kind)?;
+        let (reader, writer) = conn.split();
+        *self.reader.lock().unwrap() = Some(reader);
+        *self.writer.lock().unwrap() = Some(writer);
+        self.connected.store(true, Ordering::SeqCst);
+
+        self.negotiate_wire_format()?;
+        self.negotiate_protocol()?;
+
+        self.clone().spawn_background_reader();
+        Ok(())
+    }
+
     /// Invokes a capability on the server.
     pub fn invoke_capability(
         &self,
         capability_id: &str,
         args: HashMap<String, Value>,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
-        let result = self.send_request("invokeCapability", json!([capability_id, args]))?;
-        
+    ) -> Result<Value, AspireError> {
+        let metadata = self.default_metadata.lock().unwrap().clone();
+        self.invoke_capability_impl(capability_id, args, metadata)
+    }
+
+    /// Like `invoke_capability`, but gives up waiting once `timeout` elapses
+    /// rather than blocking indefinitely for the host's reply.
+    ///
+    /// This only bounds the wait while the calling thread is a *follower* —
+    /// parked on `pending_cv` behind another thread that is already the
+    /// connection's reader (see `drive_until`). If no other thread is
+    /// reading, this call becomes the reader itself and blocks in
+    /// `read_message` on the raw socket, which has no portable read-timeout
+    /// at the `Transport` trait level; the deadline is rechecked as soon as
+    /// that blocking read returns (whether or not it satisfied this
+    /// request), so a socket wedged mid-read can still delay the timeout by
+    /// however long that one read takes. Once the deadline passes, this
+    /// resolves to `AspireError::Canceled` — the same outcome
+    /// `invoke_capability_async`'s `timeout` argument produces — and sends
+    /// `abortCapability` for the in-flight request id so the host stops the
+    /// work rather than finishing it for a caller that already gave up.
+    pub fn invoke_capability_with_timeout(
+        self: &Arc<Self>,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+        timeout: std::time::Duration,
+    ) -> Result<Value, AspireError> {
+        let metadata = self.default_metadata.lock().unwrap().clone();
+        let request_id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let deadline = std::time::Instant::now() + timeout;
+
+        let outcome = self.invoke_capability_impl_with_id(request_id, capability_id, args, metadata, Some(deadline));
+
+        if matches!(outcome, Err(AspireError::Canceled)) {
+            let _ = self.send_request("abortCapability", json!([request_id]));
+        }
+
+        outcome
+    }
+
+    /// Like `invoke_capability`, but merges `metadata` (correlation id,
+    /// auth/bearer token, trace context, …) over this client's
+    /// `with_default_metadata` map and forwards the result alongside the
+    /// capability args, for a `*_with_context` resource method that needs to
+    /// override or add to the default for one call rather than every call.
+    pub fn invoke_capability_with_metadata(
+        &self,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+        metadata: HashMap<String, Value>,
+    ) -> Result<Value, AspireError> {
+        let mut merged = self.default_metadata.lock().unwrap().clone();
+        merged.extend(metadata);
+        self.invoke_capability_impl(capability_id, args, merged)
+    }
+
+    /// Like `invoke_capability`, but lets the caller pass a `CancellationToken`
+    /// that can be cancelled from another thread while this call is blocked
+    /// waiting on the host's reply. Cancelling sends `abortCapability` keyed
+    /// by this call's request id so the host stops the work server-side;
+    /// unlike `invoke_capability_async`, there's no second future to race
+    /// the wait against here, so this thread still waits for the host's own
+    /// reply to the original request (which, once the host honors the
+    /// abort, arrives as `AspireError::Canceled` or a `Remote` error rather
+    /// than a normal result).
+    pub fn invoke_capability_with_cancellation(
+        self: &Arc<Self>,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Value, AspireError> {
+        if let Some(token) = cancellation {
+            if token.is_cancelled() {
+                return Err(AspireError::Canceled);
+            }
+        }
+
+        let metadata = self.default_metadata.lock().unwrap().clone();
+        let request_id = self.next_id.fetch_add(1, Ordering::SeqCst);
+
+        if let Some(token) = cancellation {
+            let client = self.clone();
+            token.register(move || {
+                let _ = client.send_request("abortCapability", json!([request_id]));
+            });
+        }
+
+        self.invoke_capability_impl_with_id(request_id, capability_id, args, metadata, None)
+    }
+
+    fn invoke_capability_impl(
+        &self,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+        metadata: HashMap<String, Value>,
+    ) -> Result<Value, AspireError> {
+        let request_id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.invoke_capability_impl_with_id(request_id, capability_id, args, metadata, None)
+    }
+
+    /// Like `invoke_capability_impl`, but sends under a caller-reserved
+    /// request id rather than allocating its own — see
+    /// `send_request_with_id`. Wraps `invoke_capability_impl_body` in a
+    /// `tracing` span (capability name, handle id if the args carry one,
+    /// argument count) when the `tracing` feature is enabled, logging
+    /// elapsed time and the outcome; without that feature this compiles away
+    /// to nothing, so callers who don't pull in `tracing` pay no cost.
+    /// `deadline`, if set, bounds the sync wait for the reply — see
+    /// `invoke_capability_with_timeout`; everything else passes `None`.
+    fn invoke_capability_impl_with_id(
+        &self,
+        request_id: u64,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+        metadata: HashMap<String, Value>,
+        deadline: Option<std::time::Instant>,
+    ) -> Result<Value, AspireError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "invoke_capability",
+            capability = capability_id,
+            request_id,
+            handle = args.get("builder").and_then(|v| v.get("$handle")).and_then(|v| v.as_str()).unwrap_or(""),
+            arg_count = args.len(),
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        let started_at = std::time::Instant::now();
+
+        let outcome = self.invoke_capability_impl_body(request_id, capability_id, args, metadata, deadline);
+
+        #[cfg(feature = "tracing")]
+        match &outcome {
+            Ok(_) => tracing::debug!(elapsed_ms = started_at.elapsed().as_millis() as u64, "capability call succeeded"),
+            Err(e) => tracing::warn!(elapsed_ms = started_at.elapsed().as_millis() as u64, error = %e, "capability call failed"),
+        }
+
+        outcome
+    }
+
+    fn invoke_capability_impl_body(
+        &self,
+        request_id: u64,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+        metadata: HashMap<String, Value>,
+        deadline: Option<std::time::Instant>,
+    ) -> Result<Value, AspireError> {
+        if let Some(descriptors) = self.strict_capabilities.lock().unwrap().as_ref() {
+            validate_args(descriptors, capability_id, &args)?;
+        }
+
+        let result = self
+            .send_request_with_id_and_deadline(request_id, "invokeCapability", json!([capability_id, args, metadata]), deadline)
+            .map_err(|e| downcast_or_wrap(e))?;
+
         if is_ats_error(&result) {
             if let Value::Object(obj) = &result {
                 if let Some(Value::Object(err_obj)) = obj.get("$error") {
-                    return Err(Box::new(CapabilityError {
-                        code: err_obj
-                            .get("code")
+                    let code = err_obj.get("code").and_then(|v| v.as_str()).unwrap_or("");
+                    if code == ats_error_codes::CAPABILITY_NOT_FOUND {
+                        return Err(AspireError::CapabilityNotFound {
+                            name: capability_id.to_string(),
+                            server_version: self.negotiated_protocol().map(|p| p.server_version),
+                        });
+                    }
+                    return Err(AspireError::Remote(Box::new(RemoteError {
+                        capability: capability_id.to_string(),
+                        type_name: err_obj
+                            .get("type")
                             .and_then(|v| v.as_str())
                             .unwrap_or("")
                             .to_string(),
@@ -295,18 +2065,155 @@ impl AspireClient {
                             .and_then(|v| v.as_str())
                             .unwrap_or("")
                             .to_string(),
-                        capability: err_obj
-                            .get("capability")
+                        stack: err_obj
+                            .get("stack")
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string()),
-                    }));
+                        data: err_obj.get("data").cloned(),
+                    })));
                 }
             }
         }
-        
+
         Ok(wrap_if_handle(result, None))
     }
 
+    /// Invokes a streaming capability and returns a receiver fed by subsequent
+    /// host-pushed messages tagged with the generated subscription id, rather
+    /// than the single one-shot reply `invoke_capability` expects.
+    pub fn watch_capability(
+        &self,
+        capability_id: &str,
+        mut args: HashMap<String, Value>,
+    ) -> Result<(String, std::sync::mpsc::Receiver<Value>), AspireError> {
+        let (subscription_id, receiver) = register_subscription();
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.invoke_capability(capability_id, args) {
+            unregister_subscription(&subscription_id);
+            return Err(e);
+        }
+        Ok((subscription_id, receiver))
+    }
+
+    /// Registers `handler` to run for every host-pushed notification whose
+    /// JSON-RPC `method` is `event_name` (resource state changes, log lines,
+    /// health transitions, …), and returns a token for `unsubscribe`.
+    ///
+    /// Unlike `watch_capability`, this doesn't invoke anything -- there's no
+    /// per-call subscription id to hand the host, because these events
+    /// aren't tied to one capability invocation's lifetime. The AppHost
+    /// simply pushes a `{"method": event_name, "params": ...}` frame with no
+    /// `id` whenever the event occurs, and `read_and_dispatch_one` routes it
+    /// here (via `dispatch_event`) instead of treating it as an
+    /// `invokeCallback` request or an orphaned response. Multiple handlers
+    /// can subscribe to the same `event_name`; each gets its own token and
+    /// runs independently of the others.
+    pub fn subscribe_event<F>(&self, event_name: &str, handler: F) -> SubscriptionToken
+    where
+        F: Fn(Value) + Send + Sync + 'static,
+    {
+        register_event_handler(event_name, handler)
+    }
+
+    /// Removes the one handler `token` was issued for. Returns `false` if it
+    /// was already removed (e.g. `unsubscribe` was called twice for the same
+    /// token).
+    pub fn unsubscribe(&self, token: SubscriptionToken) -> bool {
+        unregister_event_handler(&token)
+    }
+
+    /// Async variant of `invoke_capability`, gated behind the `tokio` feature.
+    ///
+    /// The blocking round trip still runs on its own thread (the transport's
+    /// connection loop is synchronous), but the returned future resolves via a
+    /// oneshot channel rather than blocking the calling task. If `cancellation`
+    /// fires, or `timeout` elapses first, the future resolves to
+    /// `AspireError::Canceled` and an `abortCapability` message is sent for the
+    /// in-flight request id so the host stops doing the work.
+    #[cfg(feature = "tokio")]
+    pub async fn invoke_capability_async(
+        self: &Arc<Self>,
+        capability_id: &str,
+        args: HashMap<String, Value>,
+        cancellation: Option<&CancellationToken>,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Value, AspireError> {
+        let request_id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let client = self.clone();
+        let capability_id_owned = capability_id.to_string();
+        let metadata = self.default_metadata.lock().unwrap().clone();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        std::thread::spawn(move || {
+            let result = client.invoke_capability_impl_with_id(request_id, &capability_id_owned, args, metadata, None);
+            let _ = tx.send(result);
+        });
+
+        let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel();
+        if let Some(token) = cancellation {
+            let cancel_tx = Mutex::new(Some(cancel_tx));
+            token.register(move || {
+                if let Some(tx) = cancel_tx.lock().unwrap().take() {
+                    let _ = tx.send(());
+                }
+            });
+        }
+
+        let deadline = timeout.map(tokio::time::sleep);
+
+        let outcome = tokio::select! {
+            result = rx => result.unwrap_or(Err(AspireError::Canceled)),
+            _ = cancel_rx => Err(AspireError::Canceled),
+            _ = async { match deadline { Some(d) => d.await, None => std::future::pending().await } } => Err(AspireError::Canceled),
+        };
+
+        // Losing the race above leaves the blocking thread still waiting on
+        // `request_id`'s response; tell the host to stop the work rather
+        // than just abandoning the wait on our side, the same way a
+        // `CancellationToken` drop stops a callback's server-side operation.
+        if matches!(outcome, Err(AspireError::Canceled)) {
+            let _ = self.send_request("abortCapability", json!([request_id]));
+        }
+
+        outcome
+    }
+
+    /// Queues `calls` onto a `BatchBuilder` and sends them as a single
+    /// JSON-RPC batch array, returning each call's result in request order —
+    /// a convenience wrapper for callers who already have their calls as a
+    /// `Vec` and don't need `BatchBuilder::handle_ref`/`last_ref` to chain
+    /// one queued call's result into another. Reach for `self.batch()`
+    /// directly instead when calls in the batch need to reference each
+    /// other's results.
+    pub fn invoke_batch(&self, calls: Vec<(String, HashMap<String, Value>)>) -> Result<Vec<Result<Value, AspireError>>, AspireError> {
+        let mut batch = self.batch();
+        for (capability_id, args) in calls {
+            batch.call(&capability_id, args);
+        }
+        batch.send()
+    }
+
+    /// Async variant of `BatchBuilder::send`, gated behind the `tokio`
+    /// feature. Queues `calls` and flushes them as a single JSON-RPC batch
+    /// array on its own thread, same as `invoke_capability_async`, so a
+    /// chain of `with_*` mutations that don't need their results
+    /// immediately can be fired without blocking the async caller.
+    #[cfg(feature = "tokio")]
+    pub async fn send_batch_async(
+        self: &Arc<Self>,
+        calls: Vec<(String, HashMap<String, Value>)>,
+    ) -> Result<Vec<Result<Value, AspireError>>, AspireError> {
+        let client = self.clone();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        std::thread::spawn(move || {
+            let mut batch = client.batch();
+            for (capability_id, args) in calls {
+                batch.call(&capability_id, args);
+            }
+            let _ = tx.send(batch.send());
+        });
+        rx.await.map_err(|_| AspireError::Canceled)?
+    }
+
     /// Cancels a cancellation token on the server.
     pub fn cancel_token(&self, token_id: &str) -> Result<bool, Box<dyn std::error::Error>> {
         let result = self.send_request("cancelToken", json!([token_id]))?;
@@ -316,8 +2223,9 @@ impl AspireClient {
     /// Disconnects from the server.
     pub fn disconnect(&self) {
         self.connected.store(false, Ordering::SeqCst);
-        *self.conn.lock().unwrap() = None;
-        
+        *self.reader.lock().unwrap() = None;
+        *self.writer.lock().unwrap() = None;
+
         let callbacks = self.disconnect_callbacks.lock().unwrap();
         for cb in callbacks.iter() {
             cb();
@@ -326,7 +2234,27 @@ impl AspireClient {
 
     fn send_request(&self, method: &str, params: Value) -> Result<Value, Box<dyn std::error::Error>> {
         let request_id = self.next_id.fetch_add(1, Ordering::SeqCst);
-        
+        self.send_request_with_id(request_id, method, params)
+    }
+
+    /// Like `send_request`, but sends under a caller-reserved id instead of
+    /// allocating one of its own — used by `invoke_capability_async` so the
+    /// id is known before the blocking round trip starts, and a later
+    /// cancellation can reference the same id in an `abortCapability` frame.
+    fn send_request_with_id(&self, request_id: u64, method: &str, params: Value) -> Result<Value, Box<dyn std::error::Error>> {
+        self.send_request_with_id_and_deadline(request_id, method, params, None)
+    }
+
+    /// Like `send_request_with_id`, but bounds the wait for the reply by
+    /// `deadline` (see `invoke_capability_with_timeout`) instead of blocking
+    /// indefinitely.
+    fn send_request_with_id_and_deadline(
+        &self,
+        request_id: u64,
+        method: &str,
+        params: Value,
+        deadline: Option<std::time::Instant>,
+    ) -> Result<Value, Box<dyn std::error::Error>> {
         let message = json!({
             "jsonrpc": "2.0",
             "id": request_id,
@@ -334,66 +2262,287 @@ impl AspireClient {
             "params": params
         });
 
+        self.pending.lock().unwrap().in_flight.insert(request_id, message.clone());
+
         eprintln!("[Rust ATS] Sending request {} with id={}", method, request_id);
         self.write_message(&message)?;
 
+        self.wait_for_response_with_deadline(request_id, deadline)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+    }
+
+    /// Blocks until `request_id`'s response is available (optionally giving
+    /// up after `deadline` — see `invoke_capability_with_timeout`), acting as
+    /// the connection's reader (see `PendingState`) whenever no other thread
+    /// already is. Because every frame — including ones meant for other
+    /// waiters or callback invocations meant for the host — passes through
+    /// the same ordered `read_and_dispatch_one` call, a response is always
+    /// observed here before any callback frame the host sent *after* it is
+    /// even looked at, let alone dispatched.
+    fn wait_for_response_with_deadline(&self, request_id: u64, deadline: Option<std::time::Instant>) -> Result<Value, AspireError> {
+        self.drive_until(deadline, |state| state.ready.remove(&request_id))?
+    }
+
+    /// Same handoff as `wait_for_response_with_deadline`, but for a `BatchBuilder::send`
+    /// reply, which arrives as one JSON array frame rather than an
+    /// individually id-tagged object. Keyed by `base_id`, the lowest request
+    /// id in the batch — the same value the sender used to build it.
+    fn wait_for_batch(&self, base_id: u64) -> Result<Vec<Value>, AspireError> {
+        self.drive_until(None, |state| state.ready_batches.remove(&base_id))
+    }
+
+    /// Leader/follower driver shared by `wait_for_response_with_deadline`/`wait_for_batch`:
+    /// repeatedly checks whether `extract` can already satisfy this waiter
+    /// from `PendingState`; if not, either becomes the reader (if no one else
+    /// currently is) and reads+dispatches exactly one frame, or waits on
+    /// `pending_cv` for the current reader to make progress.
+    ///
+    /// `deadline`, when set, bounds how long this call waits as a
+    /// *follower* parked on `pending_cv` — each lap back to the top of the
+    /// loop checks it and gives up with `AspireError::Canceled` once it's
+    /// passed, the same outcome `invoke_capability_async`'s `timeout`
+    /// produces. It does **not** bound a lap spent as the *reader*: once this
+    /// thread claims `has_reader` and calls `read_and_dispatch_one`, it's
+    /// blocked in a synchronous socket read with no portable read-timeout at
+    /// the `Transport` trait level, so the deadline can only be rechecked
+    /// after that read returns.
+    fn drive_until<T>(&self, deadline: Option<std::time::Instant>, mut extract: impl FnMut(&mut PendingState) -> Option<T>) -> Result<T, AspireError> {
+        let mut state = self.pending.lock().unwrap();
         loop {
-            let response = self.read_message()?;
-            eprintln!("[Rust ATS] Received response: {:?}", response);
+            if let Some(value) = extract(&mut state) {
+                return Ok(value);
+            }
+            if let Some(message) = &state.conn_error {
+                return Err(AspireError::Transport(std::io::Error::new(std::io::ErrorKind::Other, message.clone())));
+            }
+            if let Some(d) = deadline {
+                if std::time::Instant::now() >= d {
+                    return Err(AspireError::Canceled);
+                }
+            }
 
-            // Check if this is a callback request from the server
-            if response.get("method").is_some() {
-                self.handle_callback_request(&response)?;
+            if state.has_reader {
+                state = match deadline {
+                    Some(d) => {
+                        let remaining = d.saturating_duration_since(std::time::Instant::now());
+                        self.pending_cv.wait_timeout(state, remaining).unwrap().0
+                    }
+                    None => self.pending_cv.wait(state).unwrap(),
+                };
                 continue;
             }
 
-            // Check if this is our response
-            if let Some(resp_id) = response.get("id").and_then(|v| v.as_u64()) {
-                if resp_id == request_id {
-                    if let Some(error) = response.get("error") {
-                        let message = error
-                            .get("message")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("Unknown error");
-                        return Err(message.into());
+            // `has_reader` stays `true` for the whole lap, including through
+            // `try_reconnect` below: clearing it before reconnecting is done
+            // would let a second thread see "no reader, no conn_error" and
+            // elect itself reader too, racing `read_and_dispatch_one` (or its
+            // own `try_reconnect`) against the reconnect already in flight.
+            state.has_reader = true;
+            drop(state);
+            let outcome = self.read_and_dispatch_one();
+            state = self.pending.lock().unwrap();
+
+            match outcome {
+                Ok(DispatchOutcome::Response(id, result)) => {
+                    state.in_flight.remove(&id);
+                    state.ready.insert(id, result);
+                    state.has_reader = false;
+                }
+                Ok(DispatchOutcome::Batch(id, entries)) => {
+                    state.ready_batches.insert(id, entries);
+                    state.has_reader = false;
+                }
+                Ok(DispatchOutcome::Dispatched) => {
+                    state.has_reader = false;
+                }
+                Err(e) => {
+                    drop(state);
+                    let reconnected = self.try_reconnect(&e);
+                    state = self.pending.lock().unwrap();
+                    if !reconnected {
+                        state.conn_error = Some(e.to_string());
                     }
-                    return Ok(response.get("result").cloned().unwrap_or(Value::Null));
+                    state.has_reader = false;
                 }
             }
+            self.pending_cv.notify_all();
+        }
+    }
+
+    /// Reads exactly one frame off the connection and classifies it:
+    /// a tagged response or batch reply is handed back to `drive_until` to
+    /// park for its waiter; a callback invocation (`dispatch_callback_frame`)
+    /// is handed to its own thread without waiting for it to finish, so one
+    /// slow callback can't stall the reader from picking up the next frame in
+    /// receive order. Subscribed events (fire-and-forget, no-`id` methods that
+    /// `dispatch_callback_frame` routes to `dispatch_event`) and
+    /// `watch_capability`-style pushes (`dispatch_notification`) run their
+    /// handlers inline on the reader thread.
+    fn read_and_dispatch_one(&self) -> Result<DispatchOutcome, AspireError> {
+        let message = self.read_message().map_err(downcast_or_wrap)?;
+        eprintln!("[Rust ATS] Received: {:?}", message);
+
+        if let Value::Array(entries) = &message {
+            let base_id = entries.iter().filter_map(|e| e.get("id").and_then(|v| v.as_u64())).min().unwrap_or(0);
+            return Ok(DispatchOutcome::Batch(base_id, entries.clone()));
         }
+
+        if message.get("method").is_some() {
+            self.dispatch_callback_frame(message);
+            return Ok(DispatchOutcome::Dispatched);
+        }
+
+        if let Some(subscription_id) = message.get("subscriptionId").and_then(|v| v.as_str()) {
+            let payload = message.get("payload").cloned().unwrap_or(Value::Null);
+            dispatch_notification(subscription_id, payload);
+            return Ok(DispatchOutcome::Dispatched);
+        }
+
+        if let Some(id) = message.get("id").and_then(|v| v.as_u64()) {
+            let result = if let Some(error) = message.get("error") {
+                Err(crate::error::rpc_error_from_value(error))
+            } else {
+                Ok(message.get("result").cloned().unwrap_or(Value::Null))
+            };
+            return Ok(DispatchOutcome::Response(id, result));
+        }
+
+        Ok(DispatchOutcome::Dispatched)
+    }
+
+    /// Hands a host→client `invokeCallback` frame off to a background thread
+    /// instead of running it inline, so the reader can go
+    /// straight back to `read_and_dispatch_one` for the next frame. Per
+    /// `callback_id` a lazily-created `Mutex` in `callback_locks` is held for
+    /// the duration of one invocation, so repeated calls to the *same*
+    /// callback never overlap or reorder relative to each other, while
+    /// invocations of *different* callback ids run on their own threads
+    /// concurrently. `cancelCallback`, pushed events and the error reply to
+    /// an unknown method are handled inline on the reader thread.
+    fn dispatch_callback_frame(&self, message: Value) {
+        let Some(client) = self.arc() else { return };
+
+        let method = message.get("method").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let request_id = message.get("id").cloned();
+
+        if method == "cancelCallback" {
+            if let Some(call_id) = message
+                .get("params")
+                .and_then(|v| v.as_array())
+                .and_then(|params| params.first())
+                .and_then(|v| v.as_u64())
+            {
+                if let Some(token) = CALLBACK_CANCELLATIONS.lock().unwrap().get(&call_id) {
+                    token.cancel();
+                }
+            }
+            return;
+        }
+
+        if method != "invokeCallback" {
+            // A fire-and-forget notification (no `id`, so the host isn't
+            // waiting on a reply) might be a pushed event someone registered
+            // for via `AspireClient::subscribe_event` -- try that before treating
+            // the method as unrecognized.
+            if request_id.is_none() {
+                let payload = message.get("params").cloned().unwrap_or(Value::Null);
+                if dispatch_event(&method, payload) {
+                    return;
+                }
+            }
+
+            if let Some(id) = request_id {
+                let _ = client.write_message(&json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {"code": -32601, "message": format!("Unknown method: {}", method)}
+                }));
+            }
+            return;
+        }
+
+        let params = message.get("params").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        let callback_id = params.get(0).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let args = params.get(1).cloned().unwrap_or(Value::Null);
+
+        {
+            let mut pending = self.pending_callback_invocations.lock().unwrap();
+            pending.push_back(CallbackInvocation {
+                callback_id: callback_id.clone(),
+                args: args.as_array().cloned().unwrap_or_default(),
+            });
+        }
+
+        // `call_id` is the host's own request id for this invocation, doubling
+        // as the monotonically increasing key `CALLBACK_CANCELLATIONS` tracks
+        // it under -- concurrent invocations of distinct callback ids (or
+        // repeat invocations of the same one) each get their own entry.
+        let call_id = request_id.as_ref().and_then(|v| v.as_u64());
+        let cancellation = args
+            .get("cancellationToken")
+            .and_then(|v| v.as_str())
+            .map(|_| Arc::new(CancellationToken::new_local()));
+        if let (Some(id), Some(token)) = (call_id, &cancellation) {
+            CALLBACK_CANCELLATIONS.lock().unwrap().insert(id, token.clone());
+        }
+
+        let lock = self
+            .callback_locks
+            .lock()
+            .unwrap()
+            .entry(callback_id.clone())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone();
+
+        std::thread::spawn(move || {
+            let _guard = lock.lock().unwrap();
+            let _scope = cancellation.map(CallbackCancellationScope::enter);
+            let result = invoke_callback(&callback_id, &args);
+
+            if let Some(id) = call_id {
+                CALLBACK_CANCELLATIONS.lock().unwrap().remove(&id);
+            }
+
+            if let Some(id) = request_id {
+                let response = match result {
+                    Ok(value) => json!({"jsonrpc": "2.0", "id": id, "result": value}),
+                    Err(e) => json!({"jsonrpc": "2.0", "id": id, "error": {"code": -32000, "message": e.to_string()}}),
+                };
+                let _ = client.write_message(&response);
+            }
+        });
     }
 
     fn write_message(&self, message: &Value) -> Result<(), Box<dyn std::error::Error>> {
-        let mut conn = self.conn.lock().unwrap();
-        let conn = conn.as_mut().ok_or("Not connected to AppHost")?;
-        
-        let body = serde_json::to_string(message)?;
+        let mut writer = self.writer.lock().unwrap();
+        let writer = writer.as_mut().ok_or_else(|| Box::new(AspireError::NotConnected) as Box<dyn std::error::Error>)?;
+
+        let body = self.wire_format.lock().unwrap().encode(message).map_err(|e| e.to_string())?;
         let header = format!("Content-Length: {}\r\n\r\n", body.len());
-        
-        conn.write_all(header.as_bytes())?;
-        conn.write_all(body.as_bytes())?;
-        conn.flush()?;
-        
+
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(&body)?;
+        writer.flush()?;
+
         Ok(())
     }
 
     fn read_message(&self) -> Result<Value, Box<dyn std::error::Error>> {
-        let mut conn = self.conn.lock().unwrap();
-        let conn = conn.as_mut().ok_or("Not connected")?;
-        
+        let mut reader = self.reader.lock().unwrap();
+        let reader = reader.as_mut().ok_or_else(|| Box::new(AspireError::NotConnected) as Box<dyn std::error::Error>)?;
+
         // Read headers
         let mut headers = HashMap::new();
-        let mut reader = BufReader::new(conn.try_clone()?);
-        
+
         loop {
             let mut line = String::new();
             reader.read_line(&mut line)?;
             let line = line.trim();
-            
+
             if line.is_empty() {
                 break;
             }
-            
+
             if let Some(idx) = line.find(':') {
                 let key = line[..idx].trim().to_lowercase();
                 let value = line[idx + 1..].trim().to_string();
@@ -406,78 +2555,163 @@ impl AspireClient {
             .get("content-length")
             .ok_or("Missing content-length")?
             .parse()?;
-        
+
         let mut body = vec![0u8; content_length];
         reader.read_exact(&mut body)?;
-        
-        let message: Value = serde_json::from_slice(&body)?;
+
+        let message = self.wire_format.lock().unwrap().decode(&body).map_err(|e| e.to_string())?;
         Ok(message)
     }
+}
 
-    fn handle_callback_request(&self, message: &Value) -> Result<(), Box<dyn std::error::Error>> {
-        let method = message
-            .get("method")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        let request_id = message.get("id").cloned();
-
-        if method != "invokeCallback" {
-            if let Some(id) = request_id {
-                self.write_message(&json!({
-                    "jsonrpc": "2.0",
-                    "id": id,
-                    "error": {"code": -32601, "message": format!("Unknown method: {}", method)}
-                }))?;
-            }
-            return Ok(());
+impl Drop for AspireClient {
+    fn drop(&mut self) {
+        let ids: Vec<String> = self.outstanding_callbacks.lock().unwrap().drain().collect();
+        for id in ids {
+            unregister_callback(&id);
+            let mut args = HashMap::new();
+            args.insert("callbackId".to_string(), Value::String(id));
+            let _ = self.invoke_capability("Aspire.Hosting/releaseCallback", args);
         }
+    }
+}
 
-        let params = message.get("params").and_then(|v| v.as_array());
-        let callback_id = params
-            .and_then(|p| p.first())
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        let args = params.and_then(|p| p.get(1)).cloned().unwrap_or(Value::Null);
-
-        let result = invoke_callback(callback_id, &args);
-        
-        match result {
-            Ok(value) => {
-                if let Some(id) = request_id {
-                    self.write_message(&json!({
-                        "jsonrpc": "2.0",
-                        "id": id,
-                        "result": value
-                    }))?;
-                }
-            }
-            Err(e) => {
-                if let Some(id) = request_id {
-                    self.write_message(&json!({
-                        "jsonrpc": "2.0",
-                        "id": id,
-                        "error": {"code": -32000, "message": e.to_string()}
-                    }))?;
-                }
-            }
+/// Queues capability calls and flushes them as a single JSON-RPC batch
+/// request, so configuring a resource with N `with_*` calls costs one round
+/// trip instead of N. Built via `AspireClient::batch`.
+pub struct BatchBuilder<'a> {
+    client: &'a AspireClient,
+    calls: Vec<(String, HashMap<String, Value>)>,
+}
+
+impl AspireClient {
+    /// Starts a batch of capability calls to flush together with `BatchBuilder::send`.
+    pub fn batch(&self) -> BatchBuilder<'_> {
+        BatchBuilder {
+            client: self,
+            calls: Vec::new(),
         }
-        
-        Ok(())
     }
 }
 
-fn invoke_callback(callback_id: &str, args: &Value) -> Result<Value, Box<dyn std::error::Error>> {
-    if callback_id.is_empty() {
-        return Err("Callback ID missing".into());
+impl<'a> BatchBuilder<'a> {
+    /// Queues a call; returns its position in the eventual result `Vec`.
+    pub fn call(&mut self, capability_id: &str, args: HashMap<String, Value>) -> usize {
+        self.calls.push((capability_id.to_string(), args));
+        self.calls.len() - 1
     }
 
-    let registry = CALLBACK_REGISTRY.lock().unwrap();
-    let callback = registry
-        .get(callback_id)
-        .ok_or_else(|| format!("Callback not found: {}", callback_id))?;
+    /// Returns a placeholder `Handle`-shaped value that refers to the result
+    /// of an earlier queued call in this same batch, by its `call` index.
+    /// Pass it anywhere a real `Handle::to_json()` would go (e.g. as a
+    /// `"builder"` argument) to chain `with_environment().with_args()...`
+    /// into one round trip — the host resolves each reference to the real
+    /// handle in queue order before invoking the call that depends on it.
+    pub fn handle_ref(&self, call_index: usize) -> Value {
+        json!({ "$batchRef": call_index })
+    }
+
+    /// `handle_ref` for the most recently queued call, for the common case of
+    /// pipelining a straight chain (`with_reference().with_endpoint()...`)
+    /// where each call only ever consumes the one right before it and the
+    /// caller would otherwise have to track `call`'s return index by hand.
+    /// Returns `None` on an empty batch.
+    pub fn last_ref(&self) -> Option<Value> {
+        self.calls.len().checked_sub(1).map(|i| self.handle_ref(i))
+    }
+
+    /// Sends all queued calls as a single JSON-RPC batch array and returns
+    /// each call's result in queue order. One entry failing does not fail the
+    /// others — per-entry failures are reported as `Err` in the result `Vec`.
+    pub fn send(self) -> Result<Vec<Result<Value, AspireError>>, AspireError> {
+        if self.calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let to_transport_err = |e: Box<dyn std::error::Error>| {
+            AspireError::Transport(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+        };
+
+        let metadata = self.client.default_metadata.lock().unwrap().clone();
+        let base_id = self.client.next_id.fetch_add(self.calls.len() as u64, Ordering::SeqCst);
+        let batch: Vec<Value> = self
+            .calls
+            .iter()
+            .enumerate()
+            .map(|(i, (capability_id, args))| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": base_id + i as u64,
+                    "method": "invokeCapability",
+                    "params": [capability_id, args, metadata],
+                })
+            })
+            .collect();
 
-    // Convert args to positional arguments
-    let positional_args: Vec<Value> = if let Value::Object(obj) = args {
+        self.client
+            .write_message(&Value::Array(batch))
+            .map_err(to_transport_err)?;
+        let entries = self.client.wait_for_batch(base_id)?;
+
+        let mut by_id: HashMap<u64, Value> = entries
+            .into_iter()
+            .filter_map(|r| r.get("id").and_then(|v| v.as_u64()).map(|id| (id, r)))
+            .collect();
+
+        Ok((0..self.calls.len())
+            .map(|i| {
+                let id = base_id + i as u64;
+                match by_id.remove(&id) {
+                    Some(r) => match r.get("error") {
+                        Some(error) => Err(AspireError::Remote(Box::new(RemoteError {
+                            capability: self.calls[i].0.clone(),
+                            type_name: String::new(),
+                            message: error.get("message").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                            stack: None,
+                            data: error.get("data").cloned(),
+                        }))),
+                        None => Ok(r.get("result").cloned().unwrap_or(Value::Null)),
+                    },
+                    None => Err(AspireError::Transport(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        "missing response for batched call",
+                    ))),
+                }
+            })
+            .collect())
+    }
+
+    /// Like `send`, but treats the batch as all-or-nothing: the first
+    /// per-entry failure is returned as the whole call's `Err` instead of
+    /// being reported positionally alongside the other entries' successes.
+    /// Use this when the queued calls depend on each other (via `handle_ref`)
+    /// such that a partial application would leave the resource in a state
+    /// the caller never asked for.
+    pub fn send_atomic(self) -> Result<Vec<Value>, AspireError> {
+        self.send()?.into_iter().collect()
+    }
+
+    /// Like `send`, but parses each successful result into a `Handle` instead
+    /// of leaving callers to pull `$handle`/`$type` out of a raw `Value` —
+    /// the common case for a batch of `add_*`/`with_*` calls chained via
+    /// `handle_ref`, where every entry's result is itself a handle the next
+    /// queued call (or the caller) resolves into a typed wrapper. A result
+    /// that isn't shaped like a handle fails with `AspireError::Serialization`
+    /// at that index, same as any other per-entry failure in the returned `Vec`.
+    pub fn send_handles(self) -> Result<Vec<Result<Handle, AspireError>>, AspireError> {
+        Ok(self
+            .send()?
+            .into_iter()
+            .map(|result| result.and_then(|value| Ok(serde_json::from_value(value)?)))
+            .collect())
+    }
+}
+
+/// Converts an `invokeCallback` frame's `args` value into the positional
+/// arguments a registered callback expects, shared by the sync and async
+/// dispatch paths in `invoke_callback`.
+fn positional_args_from(args: &Value) -> Vec<Value> {
+    if let Value::Object(obj) = args {
         let mut result = Vec::new();
         for i in 0.. {
             let key = format!("p{}", i);
@@ -492,15 +2726,163 @@ fn invoke_callback(callback_id: &str, args: &Value) -> Result<Value, Box<dyn std
         vec![args.clone()]
     } else {
         Vec::new()
+    }
+}
+
+fn invoke_callback(callback_id: &str, args: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+    if callback_id.is_empty() {
+        return Err("Callback ID missing".into());
+    }
+
+    let positional_args = positional_args_from(args);
+
+    let registry = CALLBACK_REGISTRY.lock().unwrap();
+    if let Some(callback) = registry.get(callback_id) {
+        return catch_callback_panic(std::panic::AssertUnwindSafe(|| callback(positional_args)));
+    }
+    drop(registry);
+
+    #[cfg(feature = "tokio")]
+    {
+        // The future itself runs on whichever thread calls `block_on` —
+        // here, the per-invocation thread `dispatch_callback_frame` already
+        // spawned for this `callback_id` — so `.await`ing inside the
+        // callback blocks that dedicated thread, not the connection's
+        // reader, letting the callback genuinely defer without stalling
+        // other in-flight dispatch.
+        let registry = ASYNC_CALLBACK_REGISTRY.lock().unwrap();
+        if let Some(callback) = registry.get(callback_id) {
+            let future = callback(positional_args);
+            let runtime = tokio::runtime::Runtime::new()?;
+            return catch_callback_panic(std::panic::AssertUnwindSafe(|| runtime.block_on(future)));
+        }
+    }
+
+    Err(format!("Callback not found: {}", callback_id).into())
+}
+
+/// Runs `f` (a registered callback invocation) behind `catch_unwind`, turning
+/// a panic into `AspireError::CallbackPanicked` instead of unwinding the
+/// per-invocation dispatch thread `dispatch_callback_frame` spawned — one
+/// misbehaving callback then fails only its own in-flight call.
+fn catch_callback_panic(f: impl FnOnce() -> Value + std::panic::UnwindSafe) -> Result<Value, Box<dyn std::error::Error>> {
+    std::panic::catch_unwind(f).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "callback panicked with a non-string payload".to_string());
+        Box::new(AspireError::CallbackPanicked { message }) as Box<dyn std::error::Error>
+    })
+}
+
+fn open_connection(kind: &TransportKind) -> Result<Box<dyn Transport>, Box<dyn std::error::Error>> {
+    match kind {
+        TransportKind::Socket(socket_path) => open_socket(socket_path),
+        TransportKind::Tcp(addr) => {
+            eprintln!("[Rust ATS] Opening TCP connection: {}", addr);
+            let writer = std::net::TcpStream::connect(addr)?;
+            let reader = BufReader::new(writer.try_clone()?);
+            eprintln!("[Rust ATS] TCP connection opened successfully");
+            Ok(Box::new(TcpTransport { reader, writer }))
+        }
+        TransportKind::Stdio { command, args } => {
+            eprintln!("[Rust ATS] Spawning AppHost subprocess: {} {:?}", command, args);
+            let mut child = std::process::Command::new(command)
+                .args(args)
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .spawn()?;
+            let writer = child.stdin.take().ok_or("Failed to open subprocess stdin")?;
+            let stdout = child.stdout.take().ok_or("Failed to open subprocess stdout")?;
+            eprintln!("[Rust ATS] AppHost subprocess spawned successfully");
+            Ok(Box::new(StdioTransport { child, reader: BufReader::new(stdout), writer }))
+        }
+        #[cfg(feature = "grpc")]
+        TransportKind::Grpc(endpoint) => {
+            eprintln!("[Rust ATS] Opening gRPC connection: {}", endpoint);
+            let transport = crate::grpc::GrpcTransport::connect(endpoint)?;
+            eprintln!("[Rust ATS] gRPC connection opened successfully");
+            Ok(Box::new(transport))
+        }
+        TransportKind::Ssh(config) => open_ssh_tunnel(config),
+        #[cfg(feature = "security")]
+        TransportKind::Secure { inner, security } => {
+            eprintln!("[Rust ATS] Opening secure transport, starting Noise handshake");
+            let raw = open_connection(inner)?;
+            let secured = crate::security::handshake(raw, security)?;
+            eprintln!("[Rust ATS] Noise handshake complete");
+            Ok(secured)
+        }
+    }
+}
+
+/// Shells out to the system `ssh` binary to forward `config.local_port` onto
+/// `config.remote_socket_path` on `config.host` (OpenSSH 6.7+'s
+/// `-L port:remote_socket_path` form, which forwards a local TCP port to a
+/// remote Unix socket rather than another TCP endpoint), then connects over
+/// TCP to the forwarded local port. The `ssh` child is kept running for the
+/// tunnel's lifetime; dropping the returned transport kills it.
+fn open_ssh_tunnel(config: &SshConfig) -> Result<Box<dyn Transport>, Box<dyn std::error::Error>> {
+    eprintln!("[Rust ATS] Opening SSH tunnel to {}@{}:{} -> {}", config.user, config.host, config.ssh_port, config.remote_socket_path);
+
+    let mut command = match &config.auth {
+        SshAuth::Password(password) => {
+            let mut c = std::process::Command::new("sshpass");
+            c.arg("-p").arg(password).arg("ssh");
+            c
+        }
+        SshAuth::KeyFile(_) => std::process::Command::new("ssh"),
     };
+    if let SshAuth::KeyFile(path) = &config.auth {
+        command.arg("-i").arg(path);
+    }
+    let forward = format!("{}:{}", config.local_port, config.remote_socket_path);
+    let target = format!("{}@{}", config.user, config.host);
+    let ssh_child = command
+        .arg("-N")
+        .arg("-o")
+        .arg("ExitOnForwardFailure=yes")
+        .arg("-o")
+        .arg("StrictHostKeyChecking=accept-new")
+        .arg("-p")
+        .arg(config.ssh_port.to_string())
+        .arg("-L")
+        .arg(&forward)
+        .arg(&target)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
 
-    Ok(callback(positional_args))
+    // Give the tunnel a moment to come up before dialing the local end;
+    // `ssh -N -L` prints nothing on success, so there's no readiness line to
+    // wait on, and retrying the connect a few times is simpler than parsing
+    // `-v` debug output.
+    let addr = format!("127.0.0.1:{}", config.local_port);
+    let mut last_err = None;
+    for attempt in 0..20 {
+        match std::net::TcpStream::connect(&addr) {
+            Ok(writer) => {
+                let reader = BufReader::new(writer.try_clone()?);
+                eprintln!("[Rust ATS] SSH tunnel established successfully");
+                return Ok(Box::new(SshTunnelTransport { ssh_child, reader, writer }));
+            }
+            Err(e) => {
+                last_err = Some(e);
+                if attempt < 19 {
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                }
+            }
+        }
+    }
+    Err(Box::new(last_err.unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::TimedOut, "SSH tunnel never became reachable"))))
 }
 
 #[cfg(target_os = "windows")]
-fn open_connection(socket_path: &str) -> Result<Connection, Box<dyn std::error::Error>> {
+fn open_socket(socket_path: &str) -> Result<Box<dyn Transport>, Box<dyn std::error::Error>> {
     use std::path::Path;
-    
+
     // Extract just the filename from the socket path for the named pipe
     let pipe_name = Path::new(socket_path)
         .file_name()
@@ -508,24 +2890,26 @@ fn open_connection(socket_path: &str) -> Result<Connection, Box<dyn std::error::
         .unwrap_or(socket_path);
     let pipe_path = format!("\\\\.\\pipe\\{}", pipe_name);
     eprintln!("[Rust ATS] Opening Windows named pipe: {}", pipe_path);
-    
-    let file = std::fs::OpenOptions::new()
+
+    let writer = std::fs::OpenOptions::new()
         .read(true)
         .write(true)
         .open(&pipe_path)?;
-    
+    let reader = BufReader::new(writer.try_clone()?);
+
     eprintln!("[Rust ATS] Named pipe opened successfully");
-    Ok(file)
+    Ok(Box::new(WindowsPipeTransport { reader, writer }))
 }
 
 #[cfg(not(target_os = "windows"))]
-fn open_connection(socket_path: &str) -> Result<Connection, Box<dyn std::error::Error>> {
+fn open_socket(socket_path: &str) -> Result<Box<dyn Transport>, Box<dyn std::error::Error>> {
     use std::os::unix::net::UnixStream;
-    
+
     eprintln!("[Rust ATS] Opening Unix domain socket: {}", socket_path);
-    let stream = UnixStream::connect(socket_path)?;
+    let writer = UnixStream::connect(socket_path)?;
+    let reader = BufReader::new(writer.try_clone()?);
     eprintln!("[Rust ATS] Unix domain socket opened successfully");
-    Ok(stream)
+    Ok(Box::new(UnixSocketTransport { reader, writer }))
 }
 
 /// Serializes a value to its JSON representation.
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::transport::{
-    AspireClient, CancellationToken, Handle,
+    AspireClient, CallbackGuard, CancellationToken, Handle,
     register_callback, register_cancellation, serialize_value,
 };
 use crate::base::{
@@ -80,8 +80,8 @@ pub struct TestConfigDto {
     pub port: f64,
     #[serde(rename = "Enabled")]
     pub enabled: bool,
-    #[serde(rename = "OptionalField")]
-    pub optional_field: String,
+    #[serde(rename = "OptionalField", skip_serializing_if = "Option::is_none")]
+    pub optional_field: Option<String>,
 }
 
 impl TestConfigDto {
@@ -90,7 +90,9 @@ impl TestConfigDto {
         map.insert("Name".to_string(), serde_json::to_value(&self.name).unwrap_or(Value::Null));
         map.insert("Port".to_string(), serde_json::to_value(&self.port).unwrap_or(Value::Null));
         map.insert("Enabled".to_string(), serde_json::to_value(&self.enabled).unwrap_or(Value::Null));
-        map.insert("OptionalField".to_string(), serde_json::to_value(&self.optional_field).unwrap_or(Value::Null));
+        if let Some(ref optional_field) = self.optional_field {
+            map.insert("OptionalField".to_string(), serde_json::to_value(optional_field).unwrap_or(Value::Null));
+        }
         map
     }
 }
@@ -505,6 +507,28 @@ impl TestDatabaseResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
+    /// Like `with_correlation_id`, but forwards `metadata` (trace context,
+    /// auth/bearer token, …) alongside the call via
+    /// `AspireClient::invoke_capability_with_metadata` instead of an ordinary
+    /// argument, so the host can tie this call to the rest of a fluent chain
+    /// without `correlation_id` itself being overloaded to carry both.
+    pub fn with_correlation_id_with_context(
+        &self,
+        correlation_id: &str,
+        metadata: HashMap<String, Value>,
+    ) -> Result<IResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("correlationId".to_string(), serde_json::to_value(&correlation_id).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability_with_metadata(
+            "Aspire.Hosting.CodeGeneration.Rust.Tests/withCorrelationId",
+            args,
+            metadata,
+        )?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResource::new(handle, self.client.clone()))
+    }
+
     /// Configures with optional callback
     pub fn with_optional_callback(&self, callback: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -536,15 +560,18 @@ impl TestDatabaseResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -587,15 +614,17 @@ impl TestDatabaseResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 }
 
@@ -846,15 +875,18 @@ impl TestRedisResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -937,15 +969,17 @@ impl TestRedisResource {
         Ok(serde_json::from_value(result)?)
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for the resource to be ready
@@ -1171,15 +1205,18 @@ impl TestVaultResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -1222,15 +1259,17 @@ impl TestVaultResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Configures vault using direct interface target
@@ -1261,7 +1300,7 @@ pub fn register_all_wrappers() {
 pub fn connect() -> Result<Arc<AspireClient>, Box<dyn std::error::Error>> {
     let socket_path = std::env::var("REMOTE_APP_HOST_SOCKET_PATH")
         .map_err(|_| "REMOTE_APP_HOST_SOCKET_PATH environment variable not set. Run this application using `aspire run`")?;
-    let client = Arc::new(AspireClient::new(&socket_path));
+    let client = AspireClient::new(&socket_path);
     client.connect()?;
     Ok(client)
 }
@@ -8,13 +8,16 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::transport::{
-    AspireClient, CancellationToken, Handle,
+    AspireClient, BatchBuilder, CallbackGuard, CancellationToken, Handle,
     register_callback, register_cancellation, serialize_value,
 };
+#[cfg(feature = "tokio")]
+use crate::transport::register_async_callback;
 use crate::base::{
     HandleWrapperBase, ResourceBuilderBase, ReferenceExpression,
     AspireList, AspireDict, serialize_handle, HasHandle,
 };
+use crate::error::AspireError;
 
 // ============================================================================
 // Enums
@@ -279,6 +282,31 @@ pub enum TestResourceStatus {
     Failed,
 }
 
+/// HealthStatus, as returned by `IResource::watch_health`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HealthStatus {
+    #[default]
+    #[serde(rename = "Unknown")]
+    Unknown,
+    #[serde(rename = "Healthy")]
+    Healthy,
+    #[serde(rename = "Degraded")]
+    Degraded,
+    #[serde(rename = "Unhealthy")]
+    Unhealthy,
+}
+
+impl std::fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "Unknown"),
+            Self::Healthy => write!(f, "Healthy"),
+            Self::Degraded => write!(f, "Degraded"),
+            Self::Unhealthy => write!(f, "Unhealthy"),
+        }
+    }
+}
+
 impl std::fmt::Display for TestResourceStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -290,6 +318,91 @@ impl std::fmt::Display for TestResourceStatus {
     }
 }
 
+/// UpdateFailureAction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpdateFailureAction {
+    #[default]
+    #[serde(rename = "Pause")]
+    Pause,
+    #[serde(rename = "Continue")]
+    Continue,
+    #[serde(rename = "Rollback")]
+    Rollback,
+}
+
+impl std::fmt::Display for UpdateFailureAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pause => write!(f, "Pause"),
+            Self::Continue => write!(f, "Continue"),
+            Self::Rollback => write!(f, "Rollback"),
+        }
+    }
+}
+
+/// Whether `UpdateConfig` stops the old replica of a task before starting
+/// its replacement, or starts the replacement first and only then stops
+/// the old one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpdateOrder {
+    #[default]
+    #[serde(rename = "StopFirst")]
+    StopFirst,
+    #[serde(rename = "StartFirst")]
+    StartFirst,
+}
+
+impl std::fmt::Display for UpdateOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StopFirst => write!(f, "StopFirst"),
+            Self::StartFirst => write!(f, "StartFirst"),
+        }
+    }
+}
+
+/// RestartCondition
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestartCondition {
+    #[default]
+    #[serde(rename = "None")]
+    None,
+    #[serde(rename = "OnFailure")]
+    OnFailure,
+    #[serde(rename = "Any")]
+    Any,
+}
+
+impl std::fmt::Display for RestartCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::OnFailure => write!(f, "OnFailure"),
+            Self::Any => write!(f, "Any"),
+        }
+    }
+}
+
+/// Which of a container's standard streams a `ContainerLogLine` delivered by
+/// `ContainerResource::stream_logs` came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogStreamKind {
+    #[default]
+    #[serde(rename = "Stdout")]
+    Stdout,
+    #[serde(rename = "Stderr")]
+    Stderr,
+}
+
+impl std::fmt::Display for LogStreamKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stdout => write!(f, "Stdout"),
+            Self::Stderr => write!(f, "Stderr"),
+        }
+    }
+}
+
 // ============================================================================
 // DTOs
 // ============================================================================
@@ -303,8 +416,8 @@ pub struct CreateBuilderOptions {
     pub project_directory: String,
     #[serde(rename = "AppHostFilePath")]
     pub app_host_file_path: String,
-    #[serde(rename = "ContainerRegistryOverride")]
-    pub container_registry_override: String,
+    #[serde(rename = "ContainerRegistryOverride", skip_serializing_if = "Option::is_none")]
+    pub container_registry_override: Option<String>,
     #[serde(rename = "DisableDashboard")]
     pub disable_dashboard: bool,
     #[serde(rename = "DashboardApplicationName")]
@@ -321,7 +434,9 @@ impl CreateBuilderOptions {
         map.insert("Args".to_string(), serde_json::to_value(&self.args).unwrap_or(Value::Null));
         map.insert("ProjectDirectory".to_string(), serde_json::to_value(&self.project_directory).unwrap_or(Value::Null));
         map.insert("AppHostFilePath".to_string(), serde_json::to_value(&self.app_host_file_path).unwrap_or(Value::Null));
-        map.insert("ContainerRegistryOverride".to_string(), serde_json::to_value(&self.container_registry_override).unwrap_or(Value::Null));
+        if let Some(ref container_registry_override) = self.container_registry_override {
+            map.insert("ContainerRegistryOverride".to_string(), serde_json::to_value(container_registry_override).unwrap_or(Value::Null));
+        }
         map.insert("DisableDashboard".to_string(), serde_json::to_value(&self.disable_dashboard).unwrap_or(Value::Null));
         map.insert("DashboardApplicationName".to_string(), serde_json::to_value(&self.dashboard_application_name).unwrap_or(Value::Null));
         map.insert("AllowUnsecuredTransport".to_string(), serde_json::to_value(&self.allow_unsecured_transport).unwrap_or(Value::Null));
@@ -360,6 +475,24 @@ impl ResourceEventDto {
     }
 }
 
+/// A single stdout/stderr line delivered by `ExecutableResource::subscribe_logs`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogLine {
+    #[serde(rename = "Line")]
+    pub line: String,
+    #[serde(rename = "IsError")]
+    pub is_error: bool,
+}
+
+/// A single state/health transition delivered by `IResource::subscribe_state`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceStateEvent {
+    #[serde(rename = "State")]
+    pub state: String,
+    #[serde(rename = "HealthStatus")]
+    pub health_status: String,
+}
+
 /// CommandOptions
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CommandOptions {
@@ -414,6 +547,596 @@ impl ExecuteCommandResult {
     }
 }
 
+/// UpdateConfig
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateConfig {
+    #[serde(rename = "Parallelism")]
+    pub parallelism: u32,
+    #[serde(rename = "DelaySeconds")]
+    pub delay_seconds: u32,
+    #[serde(rename = "FailureAction")]
+    pub failure_action: UpdateFailureAction,
+    #[serde(rename = "MonitorSeconds")]
+    pub monitor_seconds: u32,
+    #[serde(rename = "MaxFailureRatio")]
+    pub max_failure_ratio: f64,
+    #[serde(rename = "Order")]
+    pub order: UpdateOrder,
+}
+
+impl UpdateConfig {
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        let mut map = HashMap::new();
+        map.insert("Parallelism".to_string(), serde_json::to_value(&self.parallelism).unwrap_or(Value::Null));
+        map.insert("DelaySeconds".to_string(), serde_json::to_value(&self.delay_seconds).unwrap_or(Value::Null));
+        map.insert("FailureAction".to_string(), serde_json::to_value(&self.failure_action).unwrap_or(Value::Null));
+        map.insert("MonitorSeconds".to_string(), serde_json::to_value(&self.monitor_seconds).unwrap_or(Value::Null));
+        map.insert("MaxFailureRatio".to_string(), serde_json::to_value(&self.max_failure_ratio).unwrap_or(Value::Null));
+        map.insert("Order".to_string(), serde_json::to_value(&self.order).unwrap_or(Value::Null));
+        map
+    }
+}
+
+/// RollbackConfig
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RollbackConfig {
+    #[serde(rename = "Parallelism")]
+    pub parallelism: u32,
+    #[serde(rename = "DelaySeconds")]
+    pub delay_seconds: u32,
+    #[serde(rename = "FailureAction")]
+    pub failure_action: UpdateFailureAction,
+    #[serde(rename = "MonitorSeconds")]
+    pub monitor_seconds: u32,
+    #[serde(rename = "MaxFailureRatio")]
+    pub max_failure_ratio: f64,
+}
+
+impl RollbackConfig {
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        let mut map = HashMap::new();
+        map.insert("Parallelism".to_string(), serde_json::to_value(&self.parallelism).unwrap_or(Value::Null));
+        map.insert("DelaySeconds".to_string(), serde_json::to_value(&self.delay_seconds).unwrap_or(Value::Null));
+        map.insert("FailureAction".to_string(), serde_json::to_value(&self.failure_action).unwrap_or(Value::Null));
+        map.insert("MonitorSeconds".to_string(), serde_json::to_value(&self.monitor_seconds).unwrap_or(Value::Null));
+        map.insert("MaxFailureRatio".to_string(), serde_json::to_value(&self.max_failure_ratio).unwrap_or(Value::Null));
+        map
+    }
+}
+
+/// RestartPolicy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    #[serde(rename = "Condition")]
+    pub condition: RestartCondition,
+    #[serde(rename = "DelaySeconds")]
+    pub delay_seconds: f64,
+    #[serde(rename = "MaxAttempts")]
+    pub max_attempts: f64,
+    #[serde(rename = "WindowSeconds")]
+    pub window_seconds: f64,
+}
+
+impl RestartPolicy {
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        let mut map = HashMap::new();
+        map.insert("Condition".to_string(), serde_json::to_value(&self.condition).unwrap_or(Value::Null));
+        map.insert("DelaySeconds".to_string(), serde_json::to_value(&self.delay_seconds).unwrap_or(Value::Null));
+        map.insert("MaxAttempts".to_string(), serde_json::to_value(&self.max_attempts).unwrap_or(Value::Null));
+        map.insert("WindowSeconds".to_string(), serde_json::to_value(&self.window_seconds).unwrap_or(Value::Null));
+        map
+    }
+}
+
+/// Sparse alternative to `with_endpoint`'s eight positional `Option`
+/// parameters: built fluently via `EndpointOptionsBuilder` so callers set
+/// only the fields they care about instead of padding a call with `None`s.
+#[derive(Debug, Clone, Default)]
+pub struct EndpointOptions {
+    port: Option<f64>,
+    target_port: Option<f64>,
+    scheme: Option<String>,
+    name: Option<String>,
+    env: Option<String>,
+    is_proxied: Option<bool>,
+    is_external: Option<bool>,
+    protocol: Option<ProtocolType>,
+}
+
+impl EndpointOptions {
+    pub fn builder() -> EndpointOptionsBuilder {
+        EndpointOptionsBuilder::default()
+    }
+
+    /// Inserts only the fields that were set, mirroring the
+    /// `skip_serializing_if = "Option::is_none"` builder idiom used
+    /// elsewhere in this file, so the emitted JSON carries exactly the
+    /// explicitly-configured keys.
+    fn insert_into(&self, args: &mut HashMap<String, Value>) {
+        if let Some(ref v) = self.port {
+            args.insert("port".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.target_port {
+            args.insert("targetPort".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.scheme {
+            args.insert("scheme".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.name {
+            args.insert("name".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.env {
+            args.insert("env".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.is_proxied {
+            args.insert("isProxied".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.is_external {
+            args.insert("isExternal".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.protocol {
+            args.insert("protocol".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+    }
+}
+
+/// Fluent assembly of an [`EndpointOptions`].
+#[derive(Debug, Clone, Default)]
+pub struct EndpointOptionsBuilder {
+    port: Option<f64>,
+    target_port: Option<f64>,
+    scheme: Option<String>,
+    name: Option<String>,
+    env: Option<String>,
+    is_proxied: Option<bool>,
+    is_external: Option<bool>,
+    protocol: Option<ProtocolType>,
+}
+
+impl EndpointOptionsBuilder {
+    pub fn port(mut self, port: f64) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    pub fn target_port(mut self, target_port: f64) -> Self {
+        self.target_port = Some(target_port);
+        self
+    }
+
+    pub fn scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn env(mut self, env: impl Into<String>) -> Self {
+        self.env = Some(env.into());
+        self
+    }
+
+    pub fn is_proxied(mut self, is_proxied: bool) -> Self {
+        self.is_proxied = Some(is_proxied);
+        self
+    }
+
+    pub fn is_external(mut self, is_external: bool) -> Self {
+        self.is_external = Some(is_external);
+        self
+    }
+
+    pub fn protocol(mut self, protocol: ProtocolType) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    pub fn build(self) -> EndpointOptions {
+        EndpointOptions {
+            port: self.port,
+            target_port: self.target_port,
+            scheme: self.scheme,
+            name: self.name,
+            env: self.env,
+            is_proxied: self.is_proxied,
+            is_external: self.is_external,
+            protocol: self.protocol,
+        }
+    }
+}
+
+/// Sparse alternative to `with_http_endpoint`'s five positional `Option`
+/// parameters, built the same way as `EndpointOptions`.
+#[derive(Debug, Clone, Default)]
+pub struct HttpEndpointOptions {
+    port: Option<f64>,
+    target_port: Option<f64>,
+    name: Option<String>,
+    env: Option<String>,
+    is_proxied: Option<bool>,
+}
+
+impl HttpEndpointOptions {
+    pub fn builder() -> HttpEndpointOptionsBuilder {
+        HttpEndpointOptionsBuilder::default()
+    }
+
+    fn insert_into(&self, args: &mut HashMap<String, Value>) {
+        if let Some(ref v) = self.port {
+            args.insert("port".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.target_port {
+            args.insert("targetPort".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.name {
+            args.insert("name".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.env {
+            args.insert("env".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = self.is_proxied {
+            args.insert("isProxied".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+    }
+}
+
+/// Fluent assembly of an [`HttpEndpointOptions`].
+#[derive(Debug, Clone, Default)]
+pub struct HttpEndpointOptionsBuilder {
+    port: Option<f64>,
+    target_port: Option<f64>,
+    name: Option<String>,
+    env: Option<String>,
+    is_proxied: Option<bool>,
+}
+
+impl HttpEndpointOptionsBuilder {
+    pub fn port(mut self, port: f64) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    pub fn target_port(mut self, target_port: f64) -> Self {
+        self.target_port = Some(target_port);
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn env(mut self, env: impl Into<String>) -> Self {
+        self.env = Some(env.into());
+        self
+    }
+
+    pub fn is_proxied(mut self, is_proxied: bool) -> Self {
+        self.is_proxied = Some(is_proxied);
+        self
+    }
+
+    pub fn build(self) -> HttpEndpointOptions {
+        HttpEndpointOptions {
+            port: self.port,
+            target_port: self.target_port,
+            name: self.name,
+            env: self.env,
+            is_proxied: self.is_proxied,
+        }
+    }
+}
+
+/// Probe cadence and failure tolerance for a health check, modeled on
+/// Consul's health-check definition (`interval`/`timeout`/`deregister after`)
+/// so `with_http_health_check` and friends can share one options shape
+/// instead of each growing its own pile of positional knobs. Built fluently
+/// via `HealthCheckOptionsBuilder`, the same sparse-insert pattern as
+/// `EndpointOptions`.
+#[derive(Debug, Clone, Default)]
+pub struct HealthCheckOptions {
+    interval: Option<std::time::Duration>,
+    timeout: Option<std::time::Duration>,
+    initial_delay: Option<std::time::Duration>,
+    failure_threshold: Option<u32>,
+    success_threshold: Option<u32>,
+    deregister_critical_after: Option<std::time::Duration>,
+}
+
+impl HealthCheckOptions {
+    pub fn builder() -> HealthCheckOptionsBuilder {
+        HealthCheckOptionsBuilder::default()
+    }
+
+    fn insert_into(&self, args: &mut HashMap<String, Value>) {
+        if let Some(v) = self.interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = self.timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = self.initial_delay {
+            args.insert("initialDelayMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = self.failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = self.success_threshold {
+            args.insert("successThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = self.deregister_critical_after {
+            args.insert("deregisterCriticalAfterMs".to_string(), json!(v.as_millis() as u64));
+        }
+    }
+}
+
+/// Fluent assembly of a [`HealthCheckOptions`].
+#[derive(Debug, Clone, Default)]
+pub struct HealthCheckOptionsBuilder {
+    interval: Option<std::time::Duration>,
+    timeout: Option<std::time::Duration>,
+    initial_delay: Option<std::time::Duration>,
+    failure_threshold: Option<u32>,
+    success_threshold: Option<u32>,
+    deregister_critical_after: Option<std::time::Duration>,
+}
+
+impl HealthCheckOptionsBuilder {
+    pub fn interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Also known as Consul's `start_period`: how long a failing probe is
+    /// tolerated right after the resource starts before it counts against
+    /// `failure_threshold`.
+    pub fn initial_delay(mut self, initial_delay: std::time::Duration) -> Self {
+        self.initial_delay = Some(initial_delay);
+        self
+    }
+
+    pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = Some(failure_threshold);
+        self
+    }
+
+    pub fn success_threshold(mut self, success_threshold: u32) -> Self {
+        self.success_threshold = Some(success_threshold);
+        self
+    }
+
+    pub fn deregister_critical_after(mut self, deregister_critical_after: std::time::Duration) -> Self {
+        self.deregister_critical_after = Some(deregister_critical_after);
+        self
+    }
+
+    pub fn build(self) -> HealthCheckOptions {
+        HealthCheckOptions {
+            interval: self.interval,
+            timeout: self.timeout,
+            initial_delay: self.initial_delay,
+            failure_threshold: self.failure_threshold,
+            success_threshold: self.success_threshold,
+            deregister_critical_after: self.deregister_critical_after,
+        }
+    }
+}
+
+/// Desired vs running replica counts for a resource configured via
+/// `with_replicas`, as returned by `IResourceWithEndpoints::replica_status`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplicaStatus {
+    pub desired: u32,
+    pub running: u32,
+}
+
+/// Credentials for pulling from a private container registry, passed to
+/// `with_image_registry_auth`. Mirrors the two shapes Docker's
+/// `X-Registry-Auth` header accepts: a username/password pair, or a
+/// pre-issued identity token from an OAuth-style registry login.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RegistryAuth {
+    Password {
+        username: String,
+        password: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        email: Option<String>,
+        #[serde(rename = "serveraddress", skip_serializing_if = "Option::is_none")]
+        server_address: Option<String>,
+    },
+    Token {
+        #[serde(rename = "identitytoken")]
+        identity_token: String,
+    },
+}
+
+impl RegistryAuth {
+    pub fn builder() -> RegistryAuthBuilder {
+        RegistryAuthBuilder::default()
+    }
+
+    /// Serializes this credential to JSON and base64-encodes it, the shape
+    /// `with_image_registry_auth` sends under the args map's `"auth"` key to
+    /// match the `X-Registry-Auth` convention the backend expects.
+    pub fn to_auth_value(&self) -> Result<String, serde_json::Error> {
+        let body = serde_json::to_vec(self)?;
+        Ok(base64_encode(&body))
+    }
+}
+
+/// Fluent assembly of a [`RegistryAuth`], for callers who'd rather set
+/// fields one at a time than construct the enum variant directly.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryAuthBuilder {
+    username: Option<String>,
+    password: Option<String>,
+    email: Option<String>,
+    server_address: Option<String>,
+    identity_token: Option<String>,
+}
+
+impl RegistryAuthBuilder {
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn server_address(mut self, server_address: impl Into<String>) -> Self {
+        self.server_address = Some(server_address.into());
+        self
+    }
+
+    pub fn identity_token(mut self, identity_token: impl Into<String>) -> Self {
+        self.identity_token = Some(identity_token.into());
+        self
+    }
+
+    /// Builds the token form if `identity_token` was set, else the password
+    /// form from `username`/`password`. Panics if neither was
+    /// supplied — a `RegistryAuthBuilder` with no credentials at all is a
+    /// caller bug, not a representable `RegistryAuth`.
+    pub fn build(self) -> RegistryAuth {
+        if let Some(identity_token) = self.identity_token {
+            return RegistryAuth::Token { identity_token };
+        }
+        RegistryAuth::Password {
+            username: self.username.expect("RegistryAuthBuilder: username or identity_token is required"),
+            password: self.password.expect("RegistryAuthBuilder: password is required alongside username"),
+            email: self.email,
+            server_address: self.server_address,
+        }
+    }
+}
+
+/// Minimal standard-alphabet base64 encoder (with `=` padding) for
+/// `RegistryAuth::to_auth_value`, avoiding a dependency for one call site.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[(((b0 & 0x03) << 4) | (b1.unwrap_or(0) >> 4)) as usize] as char);
+        out.push(match b1 {
+            Some(b1) => ALPHABET[(((b1 & 0x0f) << 2) | (b2.unwrap_or(0) >> 6)) as usize] as char,
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => ALPHABET[(b2 & 0x3f) as usize] as char,
+            None => '=',
+        });
+    }
+    out
+}
+
+/// CorsOptions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorsOptions {
+    /// When `true`, any origin is allowed (`Access-Control-Allow-Origin: *`);
+    /// `allowed_origins` is ignored and must not be combined with
+    /// `allow_credentials`, since credentialed requests cannot use a wildcard.
+    #[serde(rename = "AllowAnyOrigin")]
+    pub allow_any_origin: bool,
+    /// Specific allowed origins, matched case-insensitively. The host echoes
+    /// back only the single origin that matched a given request, never a
+    /// comma-joined list.
+    #[serde(rename = "AllowedOrigins")]
+    pub allowed_origins: Vec<String>,
+    #[serde(rename = "AllowedMethods")]
+    pub allowed_methods: Vec<String>,
+    #[serde(rename = "AllowedHeaders")]
+    pub allowed_headers: Vec<String>,
+    #[serde(rename = "ExposedHeaders")]
+    pub exposed_headers: Vec<String>,
+    #[serde(rename = "AllowCredentials")]
+    pub allow_credentials: bool,
+    #[serde(rename = "MaxAgeSeconds")]
+    pub max_age_seconds: u32,
+}
+
+impl CorsOptions {
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        let mut map = HashMap::new();
+        map.insert("AllowAnyOrigin".to_string(), serde_json::to_value(&self.allow_any_origin).unwrap_or(Value::Null));
+        map.insert("AllowedOrigins".to_string(), serde_json::to_value(&self.allowed_origins).unwrap_or(Value::Null));
+        map.insert("AllowedMethods".to_string(), serde_json::to_value(&self.allowed_methods).unwrap_or(Value::Null));
+        map.insert("AllowedHeaders".to_string(), serde_json::to_value(&self.allowed_headers).unwrap_or(Value::Null));
+        map.insert("ExposedHeaders".to_string(), serde_json::to_value(&self.exposed_headers).unwrap_or(Value::Null));
+        map.insert("AllowCredentials".to_string(), serde_json::to_value(&self.allow_credentials).unwrap_or(Value::Null));
+        map.insert("MaxAgeSeconds".to_string(), serde_json::to_value(&self.max_age_seconds).unwrap_or(Value::Null));
+        map
+    }
+}
+
+/// ContainerExecResult
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerExecResult {
+    #[serde(rename = "Output")]
+    pub output: String,
+    #[serde(rename = "ExitCode")]
+    pub exit_code: i32,
+}
+
+impl ContainerExecResult {
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        let mut map = HashMap::new();
+        map.insert("Output".to_string(), serde_json::to_value(&self.output).unwrap_or(Value::Null));
+        map.insert("ExitCode".to_string(), serde_json::to_value(&self.exit_code).unwrap_or(Value::Null));
+        map
+    }
+}
+
+/// Options for `ProjectResource::logs`, adapting the container services
+/// `logs` pattern (snapshot vs. `follow`) to project diagnostics: which
+/// stream(s) to include, how much history to replay, and whether to keep
+/// streaming once the backlog is exhausted.
+#[derive(Debug, Clone)]
+pub struct LogOptions {
+    pub follow: bool,
+    pub stdout: bool,
+    pub stderr: bool,
+    pub tail: Option<u32>,
+    pub since: Option<String>,
+    /// Prefix each delivered line with the host's capture timestamp, for
+    /// `IResource::follow_logs` consumers that need ordering across streams
+    /// rather than just the raw text `ProjectResource::logs` yields.
+    pub include_timestamps: bool,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self {
+            follow: false,
+            stdout: true,
+            stderr: true,
+            tail: None,
+            since: None,
+            include_timestamps: false,
+        }
+    }
+}
+
 /// ResourceUrlAnnotation
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResourceUrlAnnotation {
@@ -447,8 +1170,8 @@ pub struct TestConfigDto {
     pub port: f64,
     #[serde(rename = "Enabled")]
     pub enabled: bool,
-    #[serde(rename = "OptionalField")]
-    pub optional_field: String,
+    #[serde(rename = "OptionalField", skip_serializing_if = "Option::is_none")]
+    pub optional_field: Option<String>,
 }
 
 impl TestConfigDto {
@@ -457,7 +1180,9 @@ impl TestConfigDto {
         map.insert("Name".to_string(), serde_json::to_value(&self.name).unwrap_or(Value::Null));
         map.insert("Port".to_string(), serde_json::to_value(&self.port).unwrap_or(Value::Null));
         map.insert("Enabled".to_string(), serde_json::to_value(&self.enabled).unwrap_or(Value::Null));
-        map.insert("OptionalField".to_string(), serde_json::to_value(&self.optional_field).unwrap_or(Value::Null));
+        if let Some(ref optional_field) = self.optional_field {
+            map.insert("OptionalField".to_string(), serde_json::to_value(optional_field).unwrap_or(Value::Null));
+        }
         map
     }
 }
@@ -504,6 +1229,61 @@ impl TestDeeplyNestedDto {
     }
 }
 
+/// Structured container introspection returned by `ContainerResource::inspect`
+/// (and `TestVaultResource::inspect`), in place of the bare status string
+/// `TestRedisResource::get_status_async` hands back.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerInspect {
+    #[serde(rename = "ContainerId")]
+    pub container_id: String,
+    #[serde(rename = "State")]
+    pub state: String,
+    #[serde(rename = "Health")]
+    pub health: String,
+    #[serde(rename = "Endpoints")]
+    pub endpoints: HashMap<String, String>,
+    #[serde(rename = "Mounts")]
+    pub mounts: Vec<String>,
+    #[serde(rename = "Env")]
+    pub env: HashMap<String, String>,
+}
+
+impl ContainerInspect {
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        let mut map = HashMap::new();
+        map.insert("ContainerId".to_string(), serde_json::to_value(&self.container_id).unwrap_or(Value::Null));
+        map.insert("State".to_string(), serde_json::to_value(&self.state).unwrap_or(Value::Null));
+        map.insert("Health".to_string(), serde_json::to_value(&self.health).unwrap_or(Value::Null));
+        map.insert("Endpoints".to_string(), serde_json::to_value(&self.endpoints).unwrap_or(Value::Null));
+        map.insert("Mounts".to_string(), serde_json::to_value(&self.mounts).unwrap_or(Value::Null));
+        map.insert("Env".to_string(), serde_json::to_value(&self.env).unwrap_or(Value::Null));
+        map
+    }
+}
+
+/// One line delivered by `ContainerResource::stream_logs`, decoded
+/// incrementally as the host pushes frames; unlike `logs`' plain `String`
+/// items, it carries the source stream and timestamp alongside the text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerLogLine {
+    #[serde(rename = "Stream")]
+    pub stream: LogStreamKind,
+    #[serde(rename = "Timestamp")]
+    pub timestamp: String,
+    #[serde(rename = "Text")]
+    pub text: String,
+}
+
+impl ContainerLogLine {
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        let mut map = HashMap::new();
+        map.insert("Stream".to_string(), serde_json::to_value(&self.stream).unwrap_or(Value::Null));
+        map.insert("Timestamp".to_string(), serde_json::to_value(&self.timestamp).unwrap_or(Value::Null));
+        map.insert("Text".to_string(), serde_json::to_value(&self.text).unwrap_or(Value::Null));
+        map
+    }
+}
+
 // ============================================================================
 // Handle Wrappers
 // ============================================================================
@@ -592,6 +1372,167 @@ impl ContainerResource {
         &self.client
     }
 
+    /// Subscribes to the container's resource lifecycle events (state, health, exit code).
+    ///
+    /// The returned `EventStream` stays open until dropped, at which point the
+    /// subscription is unregistered and the host stops pushing events.
+    pub fn subscribe_events(&self) -> Result<crate::base::EventStream<ResourceEventDto>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/subscribeResourceEvents", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unsubscribeResourceEvents",
+            self.client.clone(),
+        ))
+    }
+
+    /// Streams the container's stdout/stderr lines, modeled on Docker's logs-follow endpoint.
+    ///
+    /// With `follow=false`, the returned stream yields the currently buffered
+    /// tail and then ends. With `follow=true`, it stays open until dropped or
+    /// the container reaches a terminal `TestResourceStatus`.
+    pub fn logs(&self, follow: bool) -> Result<crate::base::EventStream<String>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("follow".to_string(), serde_json::to_value(follow).unwrap_or(Value::Null));
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/containerLogs", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unsubscribeContainerLogs",
+            self.client.clone(),
+        ))
+    }
+
+    /// Streams the container's combined stdin/stdout/stderr attach
+    /// connection, demultiplexed by stream type, instead of the
+    /// already-separated text lines `logs` yields. Use this when callers
+    /// need raw bytes (binary output, partial lines) or need to tell stdout
+    /// and stderr apart; see `attach::LogFrame`/`StreamKind`. `follow` has
+    /// the same meaning as on `logs`.
+    pub fn attach(&self, follow: bool) -> Result<crate::attach::LogFrameStream, Box<dyn std::error::Error>> {
+        Ok(crate::attach::attach_container(self.handle.to_json(), follow, self.client.clone())?)
+    }
+
+    /// Returns a typed snapshot of the container's runtime state (id, state,
+    /// health, assigned endpoints, mounts, env) instead of the bare status
+    /// string `TestRedisResource::get_status_async` returns.
+    pub fn inspect(&self) -> Result<ContainerInspect, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        let result = self.client.invoke_capability("Aspire.Hosting/inspectContainer", args)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Streams the container's stdout/stderr as structured `ContainerLogLine`s
+    /// (stream, timestamp, text), decoded incrementally as the host pushes
+    /// each line, where `logs`' `EventStream` yields plain `String`s.
+    /// `follow` has the same meaning as on
+    /// `logs`. `cancellation_token` is registered the same way as on
+    /// `get_status_async`; since the stream's `EventStream::drop` already
+    /// unsubscribes, cancelling the token and dropping the stream both stop
+    /// the host from pushing further lines.
+    pub fn stream_logs(
+        &self,
+        follow: bool,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<crate::base::EventStream<ContainerLogLine>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("follow".to_string(), serde_json::to_value(follow).unwrap_or(Value::Null));
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Some(token) = cancellation_token {
+            let token_id = register_cancellation(token, self.client.clone());
+            args.insert("cancellationToken".to_string(), Value::String(token_id));
+        }
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/streamContainerLogs", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unstreamContainerLogs",
+            self.client.clone(),
+        ))
+    }
+
+    /// Executes a command inside the running container, modeled on Docker's exec endpoint.
+    pub fn exec(&self, cmd: &[String]) -> Result<ContainerExecResult, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("cmd".to_string(), serde_json::to_value(cmd).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/containerExec", args)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Runs `count` replicas of this resource (Docker Swarm's replicated-service model).
+    pub fn with_replicas(&self, count: u32) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("count".to_string(), serde_json::to_value(count).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withReplicas", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Configures the rolling-update strategy applied across replicas.
+    pub fn with_update_config(&self, config: &UpdateConfig) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(config.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withUpdateConfig", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Configures the rollback strategy used if a rolling update fails.
+    pub fn with_rollback_config(&self, config: &RollbackConfig) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(config.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withRollbackConfig", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Configures how the container restarts on failure (Docker's service
+    /// `RestartPolicy`), for resilient behavior that pairs naturally with
+    /// `wait_for_completion` and the health-check family.
+    pub fn with_restart_policy(&self, policy: &RestartPolicy) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("policy".to_string(), serde_json::to_value(policy.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withRestartPolicy", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
+    /// Configures CORS on this resource's HTTP endpoints. A wildcard origin
+    /// (`CorsOptions::allow_any_origin`) combined with `allow_credentials` is
+    /// rejected by the host, since credentialed requests cannot use `*`.
+    pub fn with_cors(&self, options: &CorsOptions) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("options".to_string(), serde_json::to_value(options.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withCors", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Sets an environment variable
     pub fn with_environment(&self, name: &str, value: &str) -> Result<IResourceWithEnvironment, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -636,6 +1577,27 @@ impl ContainerResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
+    /// Like `with_environment_callback_async`, but `callback` returns a
+    /// `Future` instead of resolving to a `Value` immediately, so it can
+    /// genuinely `.await` work (another capability call, I/O, a timer) each
+    /// time the host invokes it rather than blocking the dedicated dispatch
+    /// thread `register_async_callback` runs it on. Gated behind the `tokio`
+    /// feature, matching `invoke_capability_async`.
+    #[cfg(feature = "tokio")]
+    pub fn with_environment_callback_future<F, Fut>(&self, callback: F) -> Result<IResourceWithEnvironment, Box<dyn std::error::Error>>
+    where
+        F: Fn(Vec<Value>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Value> + Send + 'static,
+    {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        let callback_id = register_async_callback(callback);
+        args.insert("callback".to_string(), Value::String(callback_id));
+        let result = self.client.invoke_capability("Aspire.Hosting/withEnvironmentCallbackAsync", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
+    }
+
     /// Adds arguments
     pub fn with_args(&self, args: Vec<String>) -> Result<IResourceWithArgs, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -668,6 +1630,27 @@ impl ContainerResource {
         Ok(IResourceWithArgs::new(handle, self.client.clone()))
     }
 
+    /// Like `with_args_callback_async`, but `callback` returns a `Future`
+    /// instead of resolving to a `Value` immediately, so it can genuinely
+    /// `.await` work (another capability call, I/O, a timer) each time the
+    /// host invokes it over the resource's lifetime rather than blocking the
+    /// dedicated dispatch thread `register_async_callback` runs it on.
+    /// Gated behind the `tokio` feature, matching `invoke_capability_async`.
+    #[cfg(feature = "tokio")]
+    pub fn with_args_callback_future<F, Fut>(&self, callback: F) -> Result<IResourceWithArgs, Box<dyn std::error::Error>>
+    where
+        F: Fn(Vec<Value>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Value> + Send + 'static,
+    {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        let callback_id = register_async_callback(callback);
+        args.insert("callback".to_string(), Value::String(callback_id));
+        let result = self.client.invoke_capability("Aspire.Hosting/withArgsCallbackAsync", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithArgs::new(handle, self.client.clone()))
+    }
+
     /// Adds a reference to another resource
     pub fn with_reference(&self, source: &IResourceWithConnectionString, connection_name: Option<&str>, optional: Option<bool>) -> Result<IResourceWithEnvironment, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -694,7 +1677,8 @@ impl ContainerResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Adds a network endpoint
+    /// Adds a network endpoint, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn with_endpoint(&self, port: Option<f64>, target_port: Option<f64>, scheme: Option<&str>, name: Option<&str>, env: Option<&str>, is_proxied: Option<bool>, is_external: Option<bool>, protocol: Option<ProtocolType>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
@@ -727,6 +1711,52 @@ impl ContainerResource {
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Adds a network endpoint, without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn with_endpoint(&self, port: Option<f64>, target_port: Option<f64>, scheme: Option<&str>, name: Option<&str>, env: Option<&str>, is_proxied: Option<bool>, is_external: Option<bool>, protocol: Option<ProtocolType>) -> Result<IResourceWithEndpoints, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        if let Some(ref v) = port {
+            args.insert("port".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = target_port {
+            args.insert("targetPort".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = scheme {
+            args.insert("scheme".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = name {
+            args.insert("name".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = env {
+            args.insert("env".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = is_proxied {
+            args.insert("isProxied".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = is_external {
+            args.insert("isExternal".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(ref v) = protocol {
+            args.insert("protocol".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability_async("Aspire.Hosting/withEndpoint", args, None, None).await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Like `with_endpoint`, but takes a single `EndpointOptions` built via
+    /// `EndpointOptions::builder()` instead of eight positional `Option`s,
+    /// e.g. `resource.with_endpoint_opts(EndpointOptions::builder().port(8080).scheme("https").build())`.
+    pub fn with_endpoint_opts(&self, opts: EndpointOptions) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        opts.insert_into(&mut args);
+        let result = self.client.invoke_capability("Aspire.Hosting/withEndpoint", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds an HTTP endpoint
     pub fn with_http_endpoint(&self, port: Option<f64>, target_port: Option<f64>, name: Option<&str>, env: Option<&str>, is_proxied: Option<bool>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -751,6 +1781,18 @@ impl ContainerResource {
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Like `with_http_endpoint`, but takes a single `HttpEndpointOptions`
+    /// built via `HttpEndpointOptions::builder()` instead of five positional
+    /// `Option`s.
+    pub fn with_http_endpoint_opts(&self, opts: HttpEndpointOptions) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        opts.insert_into(&mut args);
+        let result = self.client.invoke_capability("Aspire.Hosting/withHttpEndpoint", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds an HTTPS endpoint
     pub fn with_https_endpoint(&self, port: Option<f64>, target_port: Option<f64>, name: Option<&str>, env: Option<&str>, is_proxied: Option<bool>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -825,6 +1867,21 @@ impl ContainerResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
+    /// Customizes displayed URLs via a typed callback, receiving a
+    /// `ResourceUrlsCallbackContext` instead of the raw `Vec<Value>`
+    /// `with_urls_callback` hands its closure -- use `context.urls()`,
+    /// `context.cancellation_token()`, etc. instead of indexing positional
+    /// arguments by hand.
+    pub fn with_urls_callback_typed(&self, callback: impl Fn(&ResourceUrlsCallbackContext) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        let callback_id = crate::transport::register_context_callback(self.client.clone(), callback);
+        args.insert("callback".to_string(), Value::String(callback_id));
+        let result = self.client.invoke_capability("Aspire.Hosting/withUrlsCallback", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResource::new(handle, self.client.clone()))
+    }
+
     /// Adds or modifies displayed URLs
     pub fn with_url(&self, url: &str, display_text: Option<&str>) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -875,7 +1932,8 @@ impl ContainerResource {
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
-    /// Waits for another resource to be ready
+    /// Waits for another resource to be ready, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn wait_for(&self, dependency: &IResource) -> Result<IResourceWithWaitSupport, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
@@ -885,6 +1943,51 @@ impl ContainerResource {
         Ok(IResourceWithWaitSupport::new(handle, self.client.clone()))
     }
 
+    /// Waits for another resource to be ready, without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for(&self, dependency: &IResource) -> Result<IResourceWithWaitSupport, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("dependency".to_string(), dependency.handle().to_json());
+        let result = self.client.invoke_capability_async("Aspire.Hosting/waitFor", args, None, None).await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithWaitSupport::new(handle, self.client.clone()))
+    }
+
+    /// Waits for `dependency` to become healthy using the same Consul-style
+    /// blocking-query technique as `IResourceWithWaitSupport::watch_state`:
+    /// repeatedly calls `dependency.watch_health`, feeding each returned
+    /// index back into the next call, instead of a fixed sleep/poll. Returns
+    /// once the dependency reports `HealthStatus::Healthy`, along with the
+    /// index at which that was observed, so callers can chain further waits
+    /// without starting back at index 0. If `timeout` elapses first, returns
+    /// `AspireError::WaitTimeout` carrying the last index observed.
+    pub fn wait_for_healthy(
+        &self,
+        dependency: &IResource,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<(IResourceWithWaitSupport, u64), Box<dyn std::error::Error>> {
+        let deadline = timeout.map(|t| std::time::Instant::now() + t);
+        let mut last_index = 0u64;
+        loop {
+            let (index, status) = dependency.watch_health(Some(last_index))?;
+            last_index = index;
+            if status == HealthStatus::Healthy {
+                let mut args: HashMap<String, Value> = HashMap::new();
+                args.insert("builder".to_string(), self.handle.to_json());
+                args.insert("dependency".to_string(), dependency.handle().to_json());
+                let result = self.client.invoke_capability("Aspire.Hosting/waitFor", args)?;
+                let handle: Handle = serde_json::from_value(result)?;
+                return Ok((IResourceWithWaitSupport::new(handle, self.client.clone()), last_index));
+            }
+            if let Some(deadline) = deadline {
+                if std::time::Instant::now() >= deadline {
+                    return Err(AspireError::WaitTimeout { last_index }.into());
+                }
+            }
+        }
+    }
+
     /// Prevents resource from starting automatically
     pub fn with_explicit_start(&self) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -918,7 +2021,7 @@ impl ContainerResource {
     }
 
     /// Adds an HTTP health check
-    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>, options: Option<HealthCheckOptions>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
         if let Some(ref v) = path {
@@ -930,11 +2033,88 @@ impl ContainerResource {
         if let Some(ref v) = endpoint_name {
             args.insert("endpointName".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
         }
+        if let Some(ref opts) = options {
+            opts.insert_into(&mut args);
+        }
         let result = self.client.invoke_capability("Aspire.Hosting/withHttpHealthCheck", args)?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Marks the resource healthy once a TCP connect to `endpoint_name`
+    /// succeeds, for dependencies (databases, message brokers) that don't
+    /// speak HTTP. `interval`/`timeout`/`failure_threshold`/`success_threshold`
+    /// mirror the `HealthCheckOptions` knobs `with_http_health_check` takes,
+    /// so readiness gating via `wait_for` behaves the same regardless
+    /// of probe protocol: the probe only flips to passing after
+    /// `success_threshold` consecutive successes, and to failing after
+    /// `failure_threshold` consecutive failures.
+    pub fn with_tcp_health_check(
+        &self,
+        endpoint_name: &str,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+        success_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = success_threshold {
+            args.insert("successThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withTcpHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Marks the resource healthy once the standard gRPC Health Checking
+    /// protocol (`grpc.health.v1.Health/Check`) against `endpoint_name`
+    /// reports `SERVING`. `service` names the specific gRPC service to check
+    /// (the protocol's overall-server check when omitted); the same
+    /// interval/timeout/failure-threshold knobs as `with_tcp_health_check`
+    /// apply here too.
+    pub fn with_grpc_health_check(
+        &self,
+        endpoint_name: &str,
+        service: Option<&str>,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+        success_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = service {
+            args.insert("service".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = success_threshold {
+            args.insert("successThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withGrpcHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds a resource command
     pub fn with_command(&self, name: &str, display_name: &str, execute_command: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static, command_options: Option<CommandOptions>) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -988,12 +2168,39 @@ impl ContainerResource {
     pub fn with_config(&self, config: TestConfigDto) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        args.insert("config".to_string(), serde_json::to_value(&config).unwrap_or(Value::Null));
+        args.insert("config".to_string(), serde_json::to_value(&config)?);
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withConfig", args)?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResource::new(handle, self.client.clone()))
     }
 
+    /// Configures the resource with a DTO, without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn with_config_async(&self, config: TestConfigDto) -> Result<IResource, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(&config).unwrap_or(Value::Null));
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.Rust.Tests/withConfig", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResource::new(handle, self.client.clone()))
+    }
+
+    /// Like `with_config`, but appends the call to `batch` instead of
+    /// invoking it immediately, returning a placeholder reference to this
+    /// call's eventual result (`BatchBuilder::handle_ref`) rather than a
+    /// real `IResource` — pass it as a later queued call's `"builder"` arg
+    /// to chain mutations into the same `BatchBuilder::send` round trip.
+    pub fn with_config_batch(&self, batch: &mut BatchBuilder, config: TestConfigDto) -> Value {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(&config).unwrap_or(Value::Null));
+        let index = batch.call("Aspire.Hosting.CodeGeneration.Rust.Tests/withConfig", args);
+        batch.handle_ref(index)
+    }
+
     /// Configures environment with callback (test version)
     pub fn test_with_environment_callback(&self, callback: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResourceWithEnvironment, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -1005,6 +2212,33 @@ impl ContainerResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
+    /// Like `test_with_environment_callback`, but `callback` returns a
+    /// `Future` instead of resolving immediately, and the call itself
+    /// doesn't block the calling thread. The future still runs on whichever
+    /// thread `dispatch_callback_frame` dispatches this invocation on, not
+    /// necessarily a tokio worker, but it may genuinely `.await` rather than
+    /// blocking that thread for its whole duration.
+    #[cfg(feature = "tokio")]
+    pub async fn test_with_environment_callback_async<F, Fut>(
+        &self,
+        callback: F,
+    ) -> Result<IResourceWithEnvironment, AspireError>
+    where
+        F: Fn(Vec<Value>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Value> + Send + 'static,
+    {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        let callback_id = register_async_callback(callback);
+        args.insert("callback".to_string(), Value::String(callback_id));
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.Rust.Tests/testWithEnvironmentCallback", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
+    }
+
     /// Sets the created timestamp
     pub fn with_created_at(&self, created_at: &str) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -1015,6 +2249,13 @@ impl ContainerResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
+    /// Like `with_created_at`, but takes a typed `DateTime<Utc>` and
+    /// serializes it the same way `Conversion::Timestamp` would, instead of
+    /// leaving callers to format RFC3339 strings by hand.
+    pub fn with_created_at_dt(&self, created_at: chrono::DateTime<chrono::Utc>) -> Result<IResource, Box<dyn std::error::Error>> {
+        self.with_created_at(&crate::conversion::serialize_timestamp(created_at))
+    }
+
     /// Sets the modified timestamp
     pub fn with_modified_at(&self, modified_at: &str) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -1032,6 +2273,13 @@ impl ContainerResource {
         args.insert("correlationId".to_string(), serde_json::to_value(&correlation_id).unwrap_or(Value::Null));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCorrelationId", args)?;
         let handle: Handle = serde_json::from_value(result)?;
+        // Beyond this one `withCorrelationId` call, tie the id to every
+        // later `invoke_capability` this client makes, so a distributed
+        // trace can stitch calls from this resource together on the host
+        // side instead of the id only ever reaching the host once.
+        let mut correlation_metadata = HashMap::new();
+        correlation_metadata.insert("correlationId".to_string(), Value::String(correlation_id.to_string()));
+        self.client.clone().with_default_metadata(correlation_metadata);
         Ok(IResource::new(handle, self.client.clone()))
     }
 
@@ -1066,15 +2314,18 @@ impl ContainerResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -1117,15 +2368,78 @@ impl ContainerResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Sets environment variables, without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn with_environment_variables_async(&self, variables: HashMap<String, String>) -> Result<IResourceWithEnvironment, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("variables".to_string(), serde_json::to_value(&variables).unwrap_or(Value::Null));
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.Rust.Tests/withEnvironmentVariables", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
+    }
+
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
+    }
+
+    /// Like `with_cancellable_operation`, but `operation` returns a `Future`
+    /// instead of resolving to a `Value` immediately, so it can genuinely
+    /// `.await` work each time the host invokes it rather than blocking the
+    /// dedicated dispatch thread `register_async_callback` runs it on.
+    /// Gated behind the `tokio` feature, matching `invoke_capability_async`.
+    #[cfg(feature = "tokio")]
+    pub fn with_cancellable_operation_future<F, Fut>(&self, operation: F) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>>
+    where
+        F: Fn(Vec<Value>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Value> + Send + 'static,
+    {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        let guard = self.client.register_async_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
+        let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok((IResource::new(handle, self.client.clone()), guard))
+    }
+
+    /// Like `with_cancellable_operation`, but `operation` receives the
+    /// invocation's `CancellationToken` directly as a second argument instead
+    /// of reading `current_callback_cancellation()` from inside the closure
+    /// body, so a long-running operation can `token.wait()`/`is_cancelled()`
+    /// without depending on thread-local dispatch state.
+    pub fn with_cancellable_operation_token(
+        &self,
+        operation: impl Fn(Vec<Value>, Arc<crate::transport::CancellationToken>) -> Value + Send + Sync + 'static,
+    ) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        let guard = self.client.register_cancellable_callback(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
+        let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok((IResource::new(handle, self.client.clone()), guard))
+    }
+
+    /// Starts a deferred `with_*` chain against this builder: queued calls
+    /// (`deferred().queue("Aspire.Hosting.CodeGeneration.Rust.Tests/withStatus", ...).queue(...)`)
+    /// dispatch as a single `apply()` round trip instead of one RPC per call.
+    /// Prefer the eager `with_status`/`with_endpoints`/... methods above for a
+    /// single configurator; reach for this when chaining several together.
+    pub fn deferred(&self) -> crate::base::DeferredBuilder {
+        crate::base::DeferredBuilder::new(self.handle.clone(), self.client.clone())
     }
 }
 
@@ -1154,7 +2468,9 @@ impl DistributedApplication {
         &self.client
     }
 
-    /// Runs the distributed application
+    /// Runs the distributed application, blocking the current thread for its
+    /// entire lifetime.
+    #[cfg(not(feature = "tokio"))]
     pub fn run(&self, cancellation_token: Option<&CancellationToken>) -> Result<(), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("context".to_string(), self.handle.to_json());
@@ -1165,6 +2481,23 @@ impl DistributedApplication {
         let result = self.client.invoke_capability("Aspire.Hosting/run", args)?;
         Ok(())
     }
+
+    /// Runs the distributed application without blocking the calling thread,
+    /// so the caller can drive this future alongside other tasks and trigger
+    /// cancellation concurrently via `cancellation_token`.
+    #[cfg(feature = "tokio")]
+    pub async fn run(&self, cancellation_token: Option<&CancellationToken>) -> Result<(), AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        if let Some(token) = cancellation_token {
+            let token_id = register_cancellation(token, self.client.clone());
+            args.insert("cancellationToken".to_string(), Value::String(token_id));
+        }
+        self.client
+            .invoke_capability_async("Aspire.Hosting/run", args, cancellation_token, None)
+            .await?;
+        Ok(())
+    }
 }
 
 /// Wrapper for Aspire.Hosting/Aspire.Hosting.Eventing.DistributedApplicationEventSubscription
@@ -1191,6 +2524,30 @@ impl DistributedApplicationEventSubscription {
     pub fn client(&self) -> &Arc<AspireClient> {
         &self.client
     }
+
+    /// Subscribes `handler` to events delivered through this subscription.
+    ///
+    /// Issues a `subscribe`-style capability call and registers `handler` in
+    /// the callback registry under a locally generated callback id, sent to
+    /// the host as `callback`; the transport dispatches inbound notifications
+    /// to it by that id. Dropping
+    /// the returned `Subscription` sends `unsubscribe` so the host stops
+    /// pushing and frees the registry entry.
+    pub fn subscribe(&self, handler: impl Fn(Value) + Send + Sync + 'static) -> Result<crate::base::Subscription, Box<dyn std::error::Error>> {
+        let wrapped = move |args: Vec<Value>| -> Value {
+            handler(args.into_iter().next().unwrap_or(Value::Null));
+            Value::Null
+        };
+        let callback_id = register_callback(wrapped);
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("subscription".to_string(), self.handle.to_json());
+        args.insert("callback".to_string(), Value::String(callback_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting.Eventing/subscribe", args) {
+            crate::transport::unregister_callback(&callback_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::Subscription::new(callback_id, "Aspire.Hosting.Eventing/unsubscribe", self.client.clone()))
+    }
 }
 
 /// Wrapper for Aspire.Hosting/Aspire.Hosting.DistributedApplicationExecutionContext
@@ -1311,6 +2668,24 @@ impl DistributedApplicationResourceEventSubscription {
     pub fn client(&self) -> &Arc<AspireClient> {
         &self.client
     }
+
+    /// Subscribes `handler` to resource events delivered through this subscription.
+    /// See `DistributedApplicationEventSubscription::subscribe` for delivery semantics.
+    pub fn subscribe(&self, handler: impl Fn(Value) + Send + Sync + 'static) -> Result<crate::base::Subscription, Box<dyn std::error::Error>> {
+        let wrapped = move |args: Vec<Value>| -> Value {
+            handler(args.into_iter().next().unwrap_or(Value::Null));
+            Value::Null
+        };
+        let callback_id = register_callback(wrapped);
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("subscription".to_string(), self.handle.to_json());
+        args.insert("callback".to_string(), Value::String(callback_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting.Eventing/subscribeResourceEvent", args) {
+            crate::transport::unregister_callback(&callback_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::Subscription::new(callback_id, "Aspire.Hosting.Eventing/unsubscribeResourceEvent", self.client.clone()))
+    }
 }
 
 /// Wrapper for Aspire.Hosting/Aspire.Hosting.ApplicationModel.EndpointReference
@@ -1436,7 +2811,8 @@ impl EndpointReference {
         Ok(serde_json::from_value(result)?)
     }
 
-    /// Gets the URL of the endpoint asynchronously
+    /// Gets the URL of the endpoint asynchronously, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn get_value_async(&self, cancellation_token: Option<&CancellationToken>) -> Result<String, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("context".to_string(), self.handle.to_json());
@@ -1447,6 +2823,27 @@ impl EndpointReference {
         let result = self.client.invoke_capability("Aspire.Hosting.ApplicationModel/getValueAsync", args)?;
         Ok(serde_json::from_value(result)?)
     }
+
+    /// Gets the URL of the endpoint asynchronously without blocking the
+    /// calling thread, so the future can be driven concurrently with
+    /// cancellation from another task.
+    #[cfg(feature = "tokio")]
+    pub async fn get_value_async(
+        &self,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<String, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        if let Some(token) = cancellation_token {
+            let token_id = register_cancellation(token, self.client.clone());
+            args.insert("cancellationToken".to_string(), Value::String(token_id));
+        }
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.ApplicationModel/getValueAsync", args, cancellation_token, None)
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
 }
 
 /// Wrapper for Aspire.Hosting/Aspire.Hosting.ApplicationModel.EndpointReferenceExpression
@@ -1574,6 +2971,35 @@ impl ExecutableResource {
         &self.client
     }
 
+    /// Streams this executable's live log output over SSE instead of reading
+    /// it as a one-shot property, so callers get a tail rather than a snapshot.
+    pub fn stream_logs(&self) -> Result<crate::sse::SseStream, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        self.client.event_stream("Aspire.Hosting.ApplicationModel/ExecutableResource.streamLogs", args)
+    }
+
+    /// Subscribes to this executable's stdout/stderr lines over the ATS
+    /// pub/sub transport. Unlike `stream_logs`, this stays on the same
+    /// JSON-RPC connection as everything else instead of opening a separate
+    /// SSE connection; prefer it unless the host only exposes logs over SSE.
+    pub fn subscribe_logs(&self) -> Result<crate::base::EventStream<LogLine>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/subscribeLogs", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unsubscribeLogs",
+            self.client.clone(),
+        ))
+    }
+
     /// Sets the executable command
     pub fn with_executable_command(&self, command: &str) -> Result<ExecutableResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -1920,7 +3346,7 @@ impl ExecutableResource {
     }
 
     /// Adds an HTTP health check
-    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>, options: Option<HealthCheckOptions>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
         if let Some(ref v) = path {
@@ -1932,11 +3358,77 @@ impl ExecutableResource {
         if let Some(ref v) = endpoint_name {
             args.insert("endpointName".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
         }
+        if let Some(ref opts) = options {
+            opts.insert_into(&mut args);
+        }
         let result = self.client.invoke_capability("Aspire.Hosting/withHttpHealthCheck", args)?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Marks the resource healthy once a TCP connect to `endpoint_name`
+    /// succeeds, for dependencies (databases, message brokers) that don't
+    /// speak HTTP. `interval`/`timeout`/`failure_threshold` mirror the
+    /// `HealthCheckOptions` knobs `with_http_health_check` takes, so readiness
+    /// gating via `wait_for` behaves the same regardless of probe protocol.
+    pub fn with_tcp_health_check(
+        &self,
+        endpoint_name: &str,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withTcpHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Marks the resource healthy once the standard gRPC Health Checking
+    /// protocol (`grpc.health.v1.Health/Check`) against `endpoint_name`
+    /// reports `SERVING`. `service` names the specific gRPC service to check
+    /// (the protocol's overall-server check when omitted); the same
+    /// interval/timeout/failure-threshold knobs as `with_tcp_health_check`
+    /// apply here too.
+    pub fn with_grpc_health_check(
+        &self,
+        endpoint_name: &str,
+        service: Option<&str>,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = service {
+            args.insert("service".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withGrpcHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds a resource command
     pub fn with_command(&self, name: &str, display_name: &str, execute_command: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static, command_options: Option<CommandOptions>) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -2068,15 +3560,18 @@ impl ExecutableResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -2119,15 +3614,47 @@ impl ExecutableResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
+    }
+
+    /// Runs `count` replicas of this executable (Docker Swarm's replicated-service model).
+    pub fn with_replicas(&self, count: u32) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("count".to_string(), serde_json::to_value(count).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withReplicas", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Configures the rolling-update strategy applied across replicas.
+    pub fn with_update_config(&self, config: &UpdateConfig) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(config.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withUpdateConfig", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Configures the rollback strategy applied if an update fails.
+    pub fn with_rollback_config(&self, config: &RollbackConfig) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(config.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withRollbackConfig", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 }
 
@@ -2332,6 +3859,23 @@ impl IDistributedApplicationBuilder {
         Ok(TestRedisResource::new(handle, self.client.clone()))
     }
 
+    /// Adds a test Redis resource, without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn add_test_redis_async(&self, name: &str, port: Option<f64>) -> Result<TestRedisResource, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("name".to_string(), serde_json::to_value(&name).unwrap_or(Value::Null));
+        if let Some(ref v) = port {
+            args.insert("port".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.Rust.Tests/addTestRedis", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(TestRedisResource::new(handle, self.client.clone()))
+    }
+
     /// Adds a test vault resource
     pub fn add_test_vault(&self, name: &str) -> Result<TestVaultResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -2402,6 +3946,69 @@ impl IDistributedApplicationEventing {
         let result = self.client.invoke_capability("Aspire.Hosting.Eventing/IDistributedApplicationEventing.unsubscribe", args)?;
         Ok(())
     }
+
+    /// Subscribes `handler` to every `event_type` event raised by the host
+    /// eventing system, registering it as a long-lived callback via
+    /// `register_callback` (see `DistributedApplicationEventSubscription::subscribe`
+    /// for the per-subscription delivery semantics this builds on). The host
+    /// pushes notifications as they occur through the same no-reply
+    /// notification frame `EventStream` uses, rather than an `invokeCallback`
+    /// round trip, so fan-out to `handler` never blocks the host on a reply.
+    /// Returns a `DistributedApplicationEventSubscription` wrapping the
+    /// server-assigned subscription; drop it (or call `unsubscribe`) to stop
+    /// delivery.
+    pub fn subscribe(
+        &self,
+        event_type: &str,
+        handler: impl Fn(IDistributedApplicationResourceEvent) + Send + Sync + 'static,
+    ) -> Result<DistributedApplicationEventSubscription, Box<dyn std::error::Error>> {
+        let client = self.client.clone();
+        let wrapped = move |args: Vec<Value>| -> Value {
+            if let Some(value) = args.into_iter().next() {
+                if let Ok(handle) = serde_json::from_value::<Handle>(value) {
+                    handler(IDistributedApplicationResourceEvent::new(handle, client.clone()));
+                }
+            }
+            Value::Null
+        };
+        let callback_id = register_callback(wrapped);
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        args.insert("eventType".to_string(), serde_json::to_value(&event_type).unwrap_or(Value::Null));
+        args.insert("callback".to_string(), Value::String(callback_id.clone()));
+        let result = match self.client.invoke_capability("Aspire.Hosting.Eventing/IDistributedApplicationEventing.subscribe", args) {
+            Ok(result) => result,
+            Err(e) => {
+                crate::transport::unregister_callback(&callback_id);
+                return Err(e.into());
+            }
+        };
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(DistributedApplicationEventSubscription::new(handle, self.client.clone()))
+    }
+
+    /// Same as `subscribe`, but returns a pull-based `EventStream` of the raw
+    /// event handles instead of invoking a handler — for callers that prefer
+    /// iterating/polling over registering a long-lived callback. Wrap each
+    /// item with `IDistributedApplicationResourceEvent::new` to get the same
+    /// wrapper `subscribe` hands its handler.
+    pub fn subscribe_stream(&self, event_type: &str) -> Result<crate::base::EventStream<Handle>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        args.insert("eventType".to_string(), serde_json::to_value(&event_type).unwrap_or(Value::Null));
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting.Eventing/IDistributedApplicationEventing.subscribeStream", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting.Eventing/IDistributedApplicationEventing.unsubscribeStream",
+            self.client.clone(),
+        ))
+    }
 }
 
 /// Wrapper for Aspire.Hosting/Aspire.Hosting.Eventing.IDistributedApplicationResourceEvent
@@ -2454,6 +4061,110 @@ impl IResource {
     pub fn client(&self) -> &Arc<AspireClient> {
         &self.client
     }
+
+    /// Subscribes to this resource's state/health transitions. Each item is
+    /// edge-triggered — one per actual change, not a poll snapshot — and the
+    /// stream stays open until the returned `EventStream` is dropped.
+    pub fn subscribe_state(&self) -> Result<crate::base::EventStream<ResourceStateEvent>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/subscribeResourceState", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unsubscribeResourceState",
+            self.client.clone(),
+        ))
+    }
+
+    /// Performs a Consul-style blocking-query watch on this resource's health.
+    ///
+    /// Sends `last_index` (the index from a prior call, or `None`/`0` for the
+    /// current state) and blocks until the host observes a change or its
+    /// internal wait elapses. On a real change the returned index is always
+    /// strictly greater than `last_index`; on timeout it comes back
+    /// unchanged, so the caller knows to reissue the call rather than having
+    /// received a spurious event. Loop, feeding the returned index back in,
+    /// for edge-triggered health notifications with no busy-polling.
+    pub fn watch_health(&self, last_index: Option<u64>) -> Result<(u64, HealthStatus), Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("index".to_string(), json!(last_index.unwrap_or(0)));
+        let result = self.client.invoke_capability("Aspire.Hosting/watchHealth", args)?;
+        let index = result.get("index").and_then(|v| v.as_u64()).unwrap_or(last_index.unwrap_or(0));
+        let status = result
+            .get("status")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?
+            .unwrap_or_default();
+        Ok((index, status))
+    }
+
+    /// Watches this resource's overall status on a background thread using
+    /// a Consul-style blocking query, firing `callback` with the payload
+    /// each time the host-side index advances. Unlike `watch_health`, this
+    /// runs the poll loop itself rather than handing the caller an index to
+    /// feed back in. Drop the returned `Subscription` to stop the loop.
+    pub fn watch_status(&self, callback: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> crate::base::Subscription {
+        crate::base::watch_resource_status(self.handle.clone(), self.client.clone(), callback)
+    }
+
+    /// Like `watch_status`, but scoped to a single named endpoint.
+    pub fn watch_endpoint(&self, name: impl Into<String>, callback: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> crate::base::Subscription {
+        crate::base::watch_resource_endpoint(self.handle.clone(), name, self.client.clone(), callback)
+    }
+
+    /// Streams this resource's stdout/stderr to `callback` as the host emits
+    /// each line, unlike `ProjectResource::logs`'s pull-based `EventStream`.
+    /// `options.follow` controls whether the host keeps pushing after the
+    /// requested backlog (`tail`/`since`) is exhausted. Drop the returned
+    /// `Subscription` to stop the stream.
+    pub fn follow_logs(&self, options: LogOptions, callback: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<crate::base::Subscription, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("follow".to_string(), Value::Bool(options.follow));
+        args.insert("tail".to_string(), serde_json::to_value(options.tail).unwrap_or(Value::Null));
+        args.insert("since".to_string(), serde_json::to_value(options.since).unwrap_or(Value::Null));
+        args.insert("includeTimestamps".to_string(), Value::Bool(options.include_timestamps));
+        Ok(crate::base::subscribe_callback(
+            self.client.clone(),
+            "Aspire.Hosting/followLogs",
+            "Aspire.Hosting/unfollowLogs",
+            args,
+            callback,
+        )?)
+    }
+
+    /// Subscribes to an arbitrary notification topic for this resource via
+    /// the generic JSON-RPC `subscribe` capability, unlike `subscribe_state`/
+    /// `watch_status`/`follow_logs`, which are each wired to one specific
+    /// event kind. The subscription id is minted locally and sent as
+    /// `callback`; the host routes every subsequent notification carrying
+    /// that id into the returned `EventStream`; dropping it sends
+    /// `unsubscribe`.
+    pub fn subscribe_topic(&self, topic: &str) -> Result<crate::base::EventStream<Value>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("topic".to_string(), serde_json::to_value(&topic).unwrap_or(Value::Null));
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/subscribe", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unsubscribe",
+            self.client.clone(),
+        ))
+    }
 }
 
 /// Wrapper for Aspire.Hosting/Aspire.Hosting.ApplicationModel.IResourceWithArgs
@@ -2532,6 +4243,45 @@ impl IResourceWithEndpoints {
     pub fn client(&self) -> &Arc<AspireClient> {
         &self.client
     }
+
+    /// Reports how many replicas (see `with_replicas`) the host currently
+    /// has running against the desired count.
+    pub fn replica_status(&self) -> Result<ReplicaStatus, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        let result = self.client.invoke_capability("Aspire.Hosting/getReplicaStatus", args)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Blocks until `replica_status().running` reaches `desired` (or
+    /// `timeout` elapses), re-checking on every transition of the resource's
+    /// aggregate state from `watch_resource_state_stream` instead of
+    /// busy-polling `replica_status` on a fixed interval.
+    pub fn wait_for_replicas_healthy(&self, timeout: Option<std::time::Duration>) -> Result<(), Box<dyn std::error::Error>> {
+        let deadline = timeout.map(|d| std::time::Instant::now() + d);
+        let status = self.replica_status()?;
+        if status.running >= status.desired {
+            return Ok(());
+        }
+
+        let stream = crate::base::watch_resource_state_stream(
+            self.handle.handle_id.clone(),
+            self.handle.clone(),
+            self.client.clone(),
+        );
+        for _change in stream {
+            let status = self.replica_status()?;
+            if status.running >= status.desired {
+                return Ok(());
+            }
+            if let Some(deadline) = deadline {
+                if std::time::Instant::now() >= deadline {
+                    return Err("timed out waiting for replicas to become healthy".into());
+                }
+            }
+        }
+        Err("resource state stream closed before replicas became healthy".into())
+    }
 }
 
 /// Wrapper for Aspire.Hosting/Aspire.Hosting.ApplicationModel.IResourceWithEnvironment
@@ -2610,6 +4360,75 @@ impl IResourceWithWaitSupport {
     pub fn client(&self) -> &Arc<AspireClient> {
         &self.client
     }
+
+    /// Blocks until this resource's observed state advances past
+    /// `last_index` or `timeout` elapses, using the same Consul-style
+    /// blocking-query technique as `IResource::watch_health`: the host holds
+    /// the request open instead of the client busy-polling. Pass `None`/`0`
+    /// for `last_index` on the first call, which returns immediately with
+    /// the current state and index. On an internal timeout the host returns
+    /// the *same* index it was given (never lower) so the caller's loop
+    /// knows to reissue the call rather than having observed a real change;
+    /// an index lower than `last_index` would mean the host's counter was
+    /// reset out from under the caller (e.g. the resource was recreated),
+    /// which is rejected here rather than silently rewound, since rewinding
+    /// would make the next iteration re-process a state already seen.
+    pub fn watch_state(
+        &self,
+        last_index: Option<u64>,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<(u64, String), Box<dyn std::error::Error>> {
+        let last_index = last_index.unwrap_or(0);
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("index".to_string(), json!(last_index));
+        if let Some(timeout) = timeout {
+            args.insert("timeoutMs".to_string(), json!(timeout.as_millis() as u64));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/watchResourceState", args)?;
+        let index = result.get("index").and_then(|v| v.as_u64()).unwrap_or(last_index);
+        if index < last_index {
+            return Err(format!("host returned index {} lower than the {} sent", index, last_index).into());
+        }
+        let state = result.get("state").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        Ok((index, state))
+    }
+
+    /// Loops `watch_state`, feeding each response's index into the next
+    /// call, until the resource reaches `target_state` or the overall
+    /// `timeout` elapses — an edge-triggered wait with no busy-polling.
+    pub fn wait_for_state(
+        &self,
+        target_state: &str,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let deadline = timeout.map(|d| std::time::Instant::now() + d);
+        let mut index = 0;
+        loop {
+            let remaining = match deadline {
+                Some(deadline) => {
+                    let now = std::time::Instant::now();
+                    if now >= deadline {
+                        return Err(format!("timed out waiting for state \"{}\"", target_state).into());
+                    }
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+            let (new_index, state) = self.watch_state(Some(index), remaining)?;
+            if state == target_state {
+                return Ok(());
+            }
+            index = new_index;
+        }
+    }
+
+    /// Convenience over `wait_for_state` for the common "resource is up and
+    /// passing health checks" readiness `wait_for`/`wait_for_completion`
+    /// exist to express.
+    pub fn wait_until_healthy(&self, timeout: Option<std::time::Duration>) -> Result<(), Box<dyn std::error::Error>> {
+        self.wait_for_state("Running", timeout)
+    }
 }
 
 /// Wrapper for Aspire.Hosting.CodeGeneration.Rust.Tests/Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes.ITestVaultResource
@@ -2875,15 +4694,18 @@ impl ParameterResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -2916,15 +4738,17 @@ impl ParameterResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 }
 
@@ -3289,7 +5113,7 @@ impl ProjectResource {
     }
 
     /// Adds an HTTP health check
-    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>, options: Option<HealthCheckOptions>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
         if let Some(ref v) = path {
@@ -3301,11 +5125,77 @@ impl ProjectResource {
         if let Some(ref v) = endpoint_name {
             args.insert("endpointName".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
         }
+        if let Some(ref opts) = options {
+            opts.insert_into(&mut args);
+        }
         let result = self.client.invoke_capability("Aspire.Hosting/withHttpHealthCheck", args)?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Marks the resource healthy once a TCP connect to `endpoint_name`
+    /// succeeds, for dependencies (databases, message brokers) that don't
+    /// speak HTTP. `interval`/`timeout`/`failure_threshold` mirror the
+    /// `HealthCheckOptions` knobs `with_http_health_check` takes, so readiness
+    /// gating via `wait_for` behaves the same regardless of probe protocol.
+    pub fn with_tcp_health_check(
+        &self,
+        endpoint_name: &str,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withTcpHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Marks the resource healthy once the standard gRPC Health Checking
+    /// protocol (`grpc.health.v1.Health/Check`) against `endpoint_name`
+    /// reports `SERVING`. `service` names the specific gRPC service to check
+    /// (the protocol's overall-server check when omitted); the same
+    /// interval/timeout/failure-threshold knobs as `with_tcp_health_check`
+    /// apply here too.
+    pub fn with_grpc_health_check(
+        &self,
+        endpoint_name: &str,
+        service: Option<&str>,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = service {
+            args.insert("service".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withGrpcHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds a resource command
     pub fn with_command(&self, name: &str, display_name: &str, execute_command: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static, command_options: Option<CommandOptions>) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -3437,15 +5327,18 @@ impl ProjectResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -3488,15 +5381,46 @@ impl ProjectResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
+    }
+
+    /// Streams this project's stdout/stderr lines, adapting the container
+    /// services `logs` pattern (snapshot vs. `follow`) to the hosting model
+    /// so operational diagnostics aren't limited to `with_health_check`/
+    /// `with_command`. With `options.follow = false`, the returned stream
+    /// yields the backlog selected by `tail`/`since` and then ends; with
+    /// `follow = true` it stays open, emitting ordered frames until dropped.
+    pub fn logs(&self, options: LogOptions) -> Result<crate::base::EventStream<String>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("follow".to_string(), serde_json::to_value(options.follow).unwrap_or(Value::Null));
+        args.insert("stdout".to_string(), serde_json::to_value(options.stdout).unwrap_or(Value::Null));
+        args.insert("stderr".to_string(), serde_json::to_value(options.stderr).unwrap_or(Value::Null));
+        args.insert("tail".to_string(), serde_json::to_value(options.tail).unwrap_or(Value::Null));
+        args.insert("since".to_string(), serde_json::to_value(options.since).unwrap_or(Value::Null));
+        args.insert("includeTimestamps".to_string(), serde_json::to_value(options.include_timestamps).unwrap_or(Value::Null));
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/streamLogs", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unsubscribeStreamLogs",
+            self.client.clone(),
+        ))
     }
 }
 
@@ -3539,6 +5463,19 @@ impl ResourceUrlsCallbackContext {
         Ok(CancellationToken::new(handle, self.client.clone()))
     }
 
+    /// Gets the CancellationToken property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn cancellation_token_async(&self) -> Result<CancellationToken, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.ApplicationModel/ResourceUrlsCallbackContext.cancellationToken", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(CancellationToken::new(handle, self.client.clone()))
+    }
+
     /// Gets the ExecutionContext property
     pub fn execution_context(&self) -> Result<DistributedApplicationExecutionContext, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -3547,6 +5484,25 @@ impl ResourceUrlsCallbackContext {
         let handle: Handle = serde_json::from_value(result)?;
         Ok(DistributedApplicationExecutionContext::new(handle, self.client.clone()))
     }
+
+    /// Gets the ExecutionContext property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn execution_context_async(&self) -> Result<DistributedApplicationExecutionContext, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.ApplicationModel/ResourceUrlsCallbackContext.executionContext", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(DistributedApplicationExecutionContext::new(handle, self.client.clone()))
+    }
+}
+
+impl crate::transport::FromHandle for ResourceUrlsCallbackContext {
+    fn from_handle(handle: Handle, client: Arc<AspireClient>) -> Self {
+        Self::new(handle, client)
+    }
 }
 
 /// Wrapper for Aspire.Hosting.CodeGeneration.Rust.Tests/Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes.TestCallbackContext
@@ -3578,7 +5534,19 @@ impl TestCallbackContext {
     pub fn name(&self) -> Result<String, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("context".to_string(), self.handle.to_json());
-        let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.name", args)?;
+        let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.name", args)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Gets the Name property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn name_async(&self) -> Result<String, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.name", args, None, None)
+            .await?;
         Ok(serde_json::from_value(result)?)
     }
 
@@ -3592,6 +5560,20 @@ impl TestCallbackContext {
         Ok(TestCallbackContext::new(handle, self.client.clone()))
     }
 
+    /// Sets the Name property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn set_name_async(&self, value: &str) -> Result<TestCallbackContext, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        args.insert("value".to_string(), serde_json::to_value(&value).unwrap_or(Value::Null));
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.setName", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(TestCallbackContext::new(handle, self.client.clone()))
+    }
+
     /// Gets the Value property
     pub fn value(&self) -> Result<f64, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -3600,6 +5582,18 @@ impl TestCallbackContext {
         Ok(serde_json::from_value(result)?)
     }
 
+    /// Gets the Value property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn value_async(&self) -> Result<f64, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.value", args, None, None)
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     /// Sets the Value property
     pub fn set_value(&self, value: f64) -> Result<TestCallbackContext, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -3610,6 +5604,20 @@ impl TestCallbackContext {
         Ok(TestCallbackContext::new(handle, self.client.clone()))
     }
 
+    /// Sets the Value property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn set_value_async(&self, value: f64) -> Result<TestCallbackContext, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        args.insert("value".to_string(), serde_json::to_value(&value).unwrap_or(Value::Null));
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.setValue", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(TestCallbackContext::new(handle, self.client.clone()))
+    }
+
     /// Gets the CancellationToken property
     pub fn cancellation_token(&self) -> Result<CancellationToken, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -3619,6 +5627,19 @@ impl TestCallbackContext {
         Ok(CancellationToken::new(handle, self.client.clone()))
     }
 
+    /// Gets the CancellationToken property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn cancellation_token_async(&self) -> Result<CancellationToken, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.cancellationToken", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(CancellationToken::new(handle, self.client.clone()))
+    }
+
     /// Sets the CancellationToken property
     pub fn set_cancellation_token(&self, value: Option<&CancellationToken>) -> Result<TestCallbackContext, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -3631,6 +5652,23 @@ impl TestCallbackContext {
         let handle: Handle = serde_json::from_value(result)?;
         Ok(TestCallbackContext::new(handle, self.client.clone()))
     }
+
+    /// Sets the CancellationToken property without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn set_cancellation_token_async(&self, value: Option<&CancellationToken>) -> Result<TestCallbackContext, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        if let Some(token) = value {
+            let token_id = register_cancellation(token, self.client.clone());
+            args.insert("value".to_string(), Value::String(token_id));
+        }
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestCallbackContext.setCancellationToken", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(TestCallbackContext::new(handle, self.client.clone()))
+    }
 }
 
 /// Wrapper for Aspire.Hosting.CodeGeneration.Rust.Tests/Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes.TestCollectionContext
@@ -3738,6 +5776,40 @@ impl TestDatabaseResource {
         Ok(ContainerResource::new(handle, self.client.clone()))
     }
 
+    /// Supplies credentials for pulling this image from a private registry.
+    /// Serializes `auth` to JSON, base64-encodes it, and sends the result
+    /// under the args map's `"auth"` key, matching the `X-Registry-Auth`
+    /// convention the backend expects.
+    pub fn with_image_registry_auth(&self, auth: &RegistryAuth) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("auth".to_string(), Value::String(auth.to_auth_value()?));
+        let result = self.client.invoke_capability("Aspire.Hosting/withImageRegistryAuth", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
+    /// Convenience over `with_image_registry` + `with_image_registry_auth`
+    /// for the common username/password case, so callers don't have to
+    /// build a `RegistryAuth` by hand just to pull from one authenticated
+    /// registry.
+    pub fn with_image_registry_credentials(&self, registry: &str, username: &str, password: &str, email: Option<&str>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut builder = RegistryAuth::builder().username(username).password(password);
+        if let Some(email) = email {
+            builder = builder.email(email);
+        }
+        self.with_image_registry(registry)?;
+        self.with_image_registry_auth(&builder.build())
+    }
+
+    /// Like `with_image_registry_credentials`, for registries that issue a
+    /// pre-authenticated identity token (OAuth-style registry login) instead
+    /// of a username/password pair.
+    pub fn with_image_registry_token(&self, registry: &str, identity_token: &str) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        self.with_image_registry(registry)?;
+        self.with_image_registry_auth(&RegistryAuth::builder().identity_token(identity_token).build())
+    }
+
     /// Sets the container image
     pub fn with_image(&self, image: &str, tag: Option<&str>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -3751,6 +5823,23 @@ impl TestDatabaseResource {
         Ok(ContainerResource::new(handle, self.client.clone()))
     }
 
+    /// Sets the container image without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn with_image_async(&self, image: &str, tag: Option<&str>) -> Result<ContainerResource, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("image".to_string(), serde_json::to_value(&image).unwrap_or(Value::Null));
+        if let Some(ref v) = tag {
+            args.insert("tag".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting/withImage", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
     /// Adds runtime arguments for the container
     pub fn with_container_runtime_args(&self, args: Vec<String>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -4117,7 +6206,7 @@ impl TestDatabaseResource {
     }
 
     /// Adds an HTTP health check
-    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>, options: Option<HealthCheckOptions>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
         if let Some(ref v) = path {
@@ -4129,11 +6218,77 @@ impl TestDatabaseResource {
         if let Some(ref v) = endpoint_name {
             args.insert("endpointName".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
         }
+        if let Some(ref opts) = options {
+            opts.insert_into(&mut args);
+        }
         let result = self.client.invoke_capability("Aspire.Hosting/withHttpHealthCheck", args)?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Marks the resource healthy once a TCP connect to `endpoint_name`
+    /// succeeds, for dependencies (databases, message brokers) that don't
+    /// speak HTTP. `interval`/`timeout`/`failure_threshold` mirror the
+    /// `HealthCheckOptions` knobs `with_http_health_check` takes, so readiness
+    /// gating via `wait_for` behaves the same regardless of probe protocol.
+    pub fn with_tcp_health_check(
+        &self,
+        endpoint_name: &str,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withTcpHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Marks the resource healthy once the standard gRPC Health Checking
+    /// protocol (`grpc.health.v1.Health/Check`) against `endpoint_name`
+    /// reports `SERVING`. `service` names the specific gRPC service to check
+    /// (the protocol's overall-server check when omitted); the same
+    /// interval/timeout/failure-threshold knobs as `with_tcp_health_check`
+    /// apply here too.
+    pub fn with_grpc_health_check(
+        &self,
+        endpoint_name: &str,
+        service: Option<&str>,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = service {
+            args.insert("service".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withGrpcHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds a resource command
     pub fn with_command(&self, name: &str, display_name: &str, execute_command: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static, command_options: Option<CommandOptions>) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -4271,6 +6426,20 @@ impl TestDatabaseResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
+    /// Sets the resource status without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn with_status_async(&self, status: TestResourceStatus) -> Result<IResource, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("status".to_string(), serde_json::to_value(&status).unwrap_or(Value::Null));
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.Rust.Tests/withStatus", args, None, None)
+            .await?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResource::new(handle, self.client.clone()))
+    }
+
     /// Configures with nested DTO
     pub fn with_nested_config(&self, config: TestNestedDto) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -4281,17 +6450,34 @@ impl TestDatabaseResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Configures with nested DTO without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn with_nested_config_async(&self, config: TestNestedDto) -> Result<IResource, AspireError> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
-        let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
+        args.insert("config".to_string(), serde_json::to_value(&config).unwrap_or(Value::Null));
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.Rust.Tests/withNestedConfig", args, None, None)
+            .await?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResource::new(handle, self.client.clone()))
     }
 
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
+        let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok((IResource::new(handle, self.client.clone()), guard))
+    }
+
     /// Waits for another resource (test version)
     pub fn test_wait_for(&self, dependency: &IResource) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -4332,15 +6518,27 @@ impl TestDatabaseResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
+    }
+
+    /// Starts a deferred `with_*` chain against this builder: queued calls
+    /// (`deferred().queue("Aspire.Hosting/withImage", ...).queue(...)`)
+    /// dispatch as a single `apply()` round trip instead of one RPC per call.
+    /// Prefer the eager `with_image`/`with_image_tag`/`with_container_name`/...
+    /// methods above for a single configurator; reach for this when chaining
+    /// several together.
+    pub fn deferred(&self) -> crate::base::DeferredBuilder {
+        crate::base::DeferredBuilder::new(self.handle.clone(), self.client.clone())
     }
 }
 
@@ -4493,6 +6691,40 @@ impl TestRedisResource {
         Ok(ContainerResource::new(handle, self.client.clone()))
     }
 
+    /// Supplies credentials for pulling this image from a private registry.
+    /// Serializes `auth` to JSON, base64-encodes it, and sends the result
+    /// under the args map's `"auth"` key, matching the `X-Registry-Auth`
+    /// convention the backend expects.
+    pub fn with_image_registry_auth(&self, auth: &RegistryAuth) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("auth".to_string(), Value::String(auth.to_auth_value()?));
+        let result = self.client.invoke_capability("Aspire.Hosting/withImageRegistryAuth", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
+    /// Convenience over `with_image_registry` + `with_image_registry_auth`
+    /// for the common username/password case, so callers don't have to
+    /// build a `RegistryAuth` by hand just to pull from one authenticated
+    /// registry.
+    pub fn with_image_registry_credentials(&self, registry: &str, username: &str, password: &str, email: Option<&str>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut builder = RegistryAuth::builder().username(username).password(password);
+        if let Some(email) = email {
+            builder = builder.email(email);
+        }
+        self.with_image_registry(registry)?;
+        self.with_image_registry_auth(&builder.build())
+    }
+
+    /// Like `with_image_registry_credentials`, for registries that issue a
+    /// pre-authenticated identity token (OAuth-style registry login) instead
+    /// of a username/password pair.
+    pub fn with_image_registry_token(&self, registry: &str, identity_token: &str) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        self.with_image_registry(registry)?;
+        self.with_image_registry_auth(&RegistryAuth::builder().identity_token(identity_token).build())
+    }
+
     /// Sets the container image
     pub fn with_image(&self, image: &str, tag: Option<&str>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -4536,6 +6768,36 @@ impl TestRedisResource {
         Ok(ContainerResource::new(handle, self.client.clone()))
     }
 
+    /// Runs `count` replicas of this resource (Docker Swarm's replicated-service model).
+    pub fn with_replicas(&self, count: u32) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("count".to_string(), serde_json::to_value(count).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withReplicas", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
+    /// Configures the rolling-update strategy applied across replicas.
+    pub fn with_update_config(&self, config: &UpdateConfig) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(config.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withUpdateConfig", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
+    /// Configures the rollback strategy used if a rolling update fails.
+    pub fn with_rollback_config(&self, config: &RollbackConfig) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("config".to_string(), serde_json::to_value(config.to_map()).unwrap_or(Value::Null));
+        let result = self.client.invoke_capability("Aspire.Hosting/withRollbackConfig", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
     /// Sets the container name
     pub fn with_container_name(&self, name: &str) -> Result<ContainerResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -4872,7 +7134,7 @@ impl TestRedisResource {
     }
 
     /// Adds an HTTP health check
-    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>, options: Option<HealthCheckOptions>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
         if let Some(ref v) = path {
@@ -4884,11 +7146,77 @@ impl TestRedisResource {
         if let Some(ref v) = endpoint_name {
             args.insert("endpointName".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
         }
+        if let Some(ref opts) = options {
+            opts.insert_into(&mut args);
+        }
         let result = self.client.invoke_capability("Aspire.Hosting/withHttpHealthCheck", args)?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Marks the resource healthy once a TCP connect to `endpoint_name`
+    /// succeeds, for dependencies (databases, message brokers) that don't
+    /// speak HTTP. `interval`/`timeout`/`failure_threshold` mirror the
+    /// `HealthCheckOptions` knobs `with_http_health_check` takes, so readiness
+    /// gating via `wait_for` behaves the same regardless of probe protocol.
+    pub fn with_tcp_health_check(
+        &self,
+        endpoint_name: &str,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withTcpHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Marks the resource healthy once the standard gRPC Health Checking
+    /// protocol (`grpc.health.v1.Health/Check`) against `endpoint_name`
+    /// reports `SERVING`. `service` names the specific gRPC service to check
+    /// (the protocol's overall-server check when omitted); the same
+    /// interval/timeout/failure-threshold knobs as `with_tcp_health_check`
+    /// apply here too.
+    pub fn with_grpc_health_check(
+        &self,
+        endpoint_name: &str,
+        service: Option<&str>,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = service {
+            args.insert("service".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withGrpcHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds a resource command
     pub fn with_command(&self, name: &str, display_name: &str, execute_command: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static, command_options: Option<CommandOptions>) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -5081,15 +7409,18 @@ impl TestRedisResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -5160,7 +7491,8 @@ impl TestRedisResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Gets the status of the resource asynchronously
+    /// Gets the status of the resource asynchronously, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn get_status_async(&self, cancellation_token: Option<&CancellationToken>) -> Result<String, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
@@ -5172,18 +7504,37 @@ impl TestRedisResource {
         Ok(serde_json::from_value(result)?)
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Gets the status of the resource asynchronously, without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn get_status_async(&self, cancellation_token: Option<&CancellationToken>) -> Result<String, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        if let Some(token) = cancellation_token {
+            let token_id = register_cancellation(token, self.client.clone());
+            args.insert("cancellationToken".to_string(), Value::String(token_id));
+        }
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.Rust.Tests/getStatusAsync", args, cancellation_token, None)
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
-    /// Waits for the resource to be ready
+    /// Waits for the resource to be ready, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn wait_for_ready_async(&self, timeout: f64, cancellation_token: Option<&CancellationToken>) -> Result<bool, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
@@ -5195,6 +7546,32 @@ impl TestRedisResource {
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/waitForReadyAsync", args)?;
         Ok(serde_json::from_value(result)?)
     }
+
+    /// Waits for the resource to be ready, without blocking the calling
+    /// thread. `timeout` is enforced both remotely (sent as an argument) and
+    /// client-side — `invoke_capability_async` races the RPC future against
+    /// its own `tokio::time::sleep(timeout)`, so a host that never replies
+    /// still resolves this future on time.
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for_ready_async(&self, timeout: f64, cancellation_token: Option<&CancellationToken>) -> Result<bool, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("timeout".to_string(), serde_json::to_value(&timeout).unwrap_or(Value::Null));
+        if let Some(token) = cancellation_token {
+            let token_id = register_cancellation(token, self.client.clone());
+            args.insert("cancellationToken".to_string(), Value::String(token_id));
+        }
+        let result = self
+            .client
+            .invoke_capability_async(
+                "Aspire.Hosting.CodeGeneration.Rust.Tests/waitForReadyAsync",
+                args,
+                cancellation_token,
+                Some(std::time::Duration::from_secs_f64(timeout)),
+            )
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
 }
 
 /// Wrapper for Aspire.Hosting.CodeGeneration.Rust.Tests/Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes.TestResourceContext
@@ -5258,7 +7635,8 @@ impl TestResourceContext {
         Ok(TestResourceContext::new(handle, self.client.clone()))
     }
 
-    /// Invokes the GetValueAsync method
+    /// Invokes the GetValueAsync method, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn get_value_async(&self) -> Result<String, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("context".to_string(), self.handle.to_json());
@@ -5266,22 +7644,61 @@ impl TestResourceContext {
         Ok(serde_json::from_value(result)?)
     }
 
-    /// Invokes the SetValueAsync method
+    /// Invokes the GetValueAsync method without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn get_value_async(&self) -> Result<String, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestResourceContext.getValueAsync", args, None, None)
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Invokes the SetValueAsync method, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn set_value_async(&self, value: &str) -> Result<(), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("context".to_string(), self.handle.to_json());
         args.insert("value".to_string(), serde_json::to_value(&value).unwrap_or(Value::Null));
-        let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestResourceContext.setValueAsync", args)?;
+        let _result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestResourceContext.setValueAsync", args)?;
+        Ok(())
+    }
+
+    /// Invokes the SetValueAsync method without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn set_value_async(&self, value: &str) -> Result<(), AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        args.insert("value".to_string(), serde_json::to_value(&value).unwrap_or(Value::Null));
+        let _result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestResourceContext.setValueAsync", args, None, None)
+            .await?;
         Ok(())
     }
 
-    /// Invokes the ValidateAsync method
+    /// Invokes the ValidateAsync method, blocking the current thread.
+    #[cfg(not(feature = "tokio"))]
     pub fn validate_async(&self) -> Result<bool, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("context".to_string(), self.handle.to_json());
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestResourceContext.validateAsync", args)?;
         Ok(serde_json::from_value(result)?)
     }
+
+    /// Invokes the ValidateAsync method without blocking the calling thread.
+    #[cfg(feature = "tokio")]
+    pub async fn validate_async(&self) -> Result<bool, AspireError> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("context".to_string(), self.handle.to_json());
+        let result = self
+            .client
+            .invoke_capability_async("Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes/TestResourceContext.validateAsync", args, None, None)
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
 }
 
 /// Wrapper for Aspire.Hosting.CodeGeneration.Rust.Tests/Aspire.Hosting.CodeGeneration.TypeScript.Tests.TestTypes.TestVaultResource
@@ -5309,6 +7726,45 @@ impl TestVaultResource {
         &self.client
     }
 
+    /// Returns a typed snapshot of the vault container's runtime state (id,
+    /// state, health, assigned endpoints, mounts, env). See
+    /// `ContainerResource::inspect` for the shared `ContainerInspect` shape.
+    pub fn inspect(&self) -> Result<ContainerInspect, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        let result = self.client.invoke_capability("Aspire.Hosting/inspectContainer", args)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Streams the vault container's stdout/stderr as structured
+    /// `ContainerLogLine`s. See `ContainerResource::stream_logs` for the
+    /// follow/cancellation semantics, which this shares exactly.
+    pub fn stream_logs(
+        &self,
+        follow: bool,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<crate::base::EventStream<ContainerLogLine>, Box<dyn std::error::Error>> {
+        let (subscription_id, receiver) = crate::transport::register_subscription();
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("resource".to_string(), self.handle.to_json());
+        args.insert("follow".to_string(), serde_json::to_value(follow).unwrap_or(Value::Null));
+        args.insert("callback".to_string(), Value::String(subscription_id.clone()));
+        if let Some(token) = cancellation_token {
+            let token_id = register_cancellation(token, self.client.clone());
+            args.insert("cancellationToken".to_string(), Value::String(token_id));
+        }
+        if let Err(e) = self.client.invoke_capability("Aspire.Hosting/streamContainerLogs", args) {
+            crate::transport::unregister_subscription(&subscription_id);
+            return Err(e.into());
+        }
+        Ok(crate::base::EventStream::new(
+            receiver,
+            subscription_id,
+            "Aspire.Hosting/unstreamContainerLogs",
+            self.client.clone(),
+        ))
+    }
+
     /// Adds a bind mount
     pub fn with_bind_mount(&self, source: &str, target: &str, is_read_only: Option<bool>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -5353,6 +7809,40 @@ impl TestVaultResource {
         Ok(ContainerResource::new(handle, self.client.clone()))
     }
 
+    /// Supplies credentials for pulling this image from a private registry.
+    /// Serializes `auth` to JSON, base64-encodes it, and sends the result
+    /// under the args map's `"auth"` key, matching the `X-Registry-Auth`
+    /// convention the backend expects.
+    pub fn with_image_registry_auth(&self, auth: &RegistryAuth) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("auth".to_string(), Value::String(auth.to_auth_value()?));
+        let result = self.client.invoke_capability("Aspire.Hosting/withImageRegistryAuth", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(ContainerResource::new(handle, self.client.clone()))
+    }
+
+    /// Convenience over `with_image_registry` + `with_image_registry_auth`
+    /// for the common username/password case, so callers don't have to
+    /// build a `RegistryAuth` by hand just to pull from one authenticated
+    /// registry.
+    pub fn with_image_registry_credentials(&self, registry: &str, username: &str, password: &str, email: Option<&str>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        let mut builder = RegistryAuth::builder().username(username).password(password);
+        if let Some(email) = email {
+            builder = builder.email(email);
+        }
+        self.with_image_registry(registry)?;
+        self.with_image_registry_auth(&builder.build())
+    }
+
+    /// Like `with_image_registry_credentials`, for registries that issue a
+    /// pre-authenticated identity token (OAuth-style registry login) instead
+    /// of a username/password pair.
+    pub fn with_image_registry_token(&self, registry: &str, identity_token: &str) -> Result<ContainerResource, Box<dyn std::error::Error>> {
+        self.with_image_registry(registry)?;
+        self.with_image_registry_auth(&RegistryAuth::builder().identity_token(identity_token).build())
+    }
+
     /// Sets the container image
     pub fn with_image(&self, image: &str, tag: Option<&str>) -> Result<ContainerResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -5732,7 +8222,7 @@ impl TestVaultResource {
     }
 
     /// Adds an HTTP health check
-    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+    pub fn with_http_health_check(&self, path: Option<&str>, status_code: Option<f64>, endpoint_name: Option<&str>, options: Option<HealthCheckOptions>) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
         if let Some(ref v) = path {
@@ -5744,11 +8234,77 @@ impl TestVaultResource {
         if let Some(ref v) = endpoint_name {
             args.insert("endpointName".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
         }
+        if let Some(ref opts) = options {
+            opts.insert_into(&mut args);
+        }
         let result = self.client.invoke_capability("Aspire.Hosting/withHttpHealthCheck", args)?;
         let handle: Handle = serde_json::from_value(result)?;
         Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
     }
 
+    /// Marks the resource healthy once a TCP connect to `endpoint_name`
+    /// succeeds, for dependencies (databases, message brokers) that don't
+    /// speak HTTP. `interval`/`timeout`/`failure_threshold` mirror the
+    /// `HealthCheckOptions` knobs `with_http_health_check` takes, so readiness
+    /// gating via `wait_for` behaves the same regardless of probe protocol.
+    pub fn with_tcp_health_check(
+        &self,
+        endpoint_name: &str,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withTcpHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
+    /// Marks the resource healthy once the standard gRPC Health Checking
+    /// protocol (`grpc.health.v1.Health/Check`) against `endpoint_name`
+    /// reports `SERVING`. `service` names the specific gRPC service to check
+    /// (the protocol's overall-server check when omitted); the same
+    /// interval/timeout/failure-threshold knobs as `with_tcp_health_check`
+    /// apply here too.
+    pub fn with_grpc_health_check(
+        &self,
+        endpoint_name: &str,
+        service: Option<&str>,
+        interval: Option<std::time::Duration>,
+        timeout: Option<std::time::Duration>,
+        failure_threshold: Option<u32>,
+    ) -> Result<IResourceWithEndpoints, Box<dyn std::error::Error>> {
+        let mut args: HashMap<String, Value> = HashMap::new();
+        args.insert("builder".to_string(), self.handle.to_json());
+        args.insert("endpointName".to_string(), serde_json::to_value(endpoint_name).unwrap_or(Value::Null));
+        if let Some(v) = service {
+            args.insert("service".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        if let Some(v) = interval {
+            args.insert("intervalMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = timeout {
+            args.insert("timeoutMs".to_string(), json!(v.as_millis() as u64));
+        }
+        if let Some(v) = failure_threshold {
+            args.insert("failureThreshold".to_string(), serde_json::to_value(v).unwrap_or(Value::Null));
+        }
+        let result = self.client.invoke_capability("Aspire.Hosting/withGrpcHealthCheck", args)?;
+        let handle: Handle = serde_json::from_value(result)?;
+        Ok(IResourceWithEndpoints::new(handle, self.client.clone()))
+    }
+
     /// Adds a resource command
     pub fn with_command(&self, name: &str, display_name: &str, execute_command: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static, command_options: Option<CommandOptions>) -> Result<IResource, Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
@@ -5896,15 +8452,18 @@ impl TestVaultResource {
         Ok(IResource::new(handle, self.client.clone()))
     }
 
-    /// Adds validation callback
-    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Adds validation callback. Returns the callback's `CallbackGuard`
+    /// alongside the builder — drop it to release the callback (e.g. when
+    /// replacing the validator on a later reconfiguration), or call
+    /// `.leak()` to keep it registered for the resource's lifetime.
+    pub fn with_validator(&self, validator: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(validator);
-        args.insert("validator".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(validator);
+        args.insert("validator".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withValidator", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Waits for another resource (test version)
@@ -5947,15 +8506,17 @@ impl TestVaultResource {
         Ok(IResourceWithEnvironment::new(handle, self.client.clone()))
     }
 
-    /// Performs a cancellable operation
-    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<IResource, Box<dyn std::error::Error>> {
+    /// Performs a cancellable operation. Returns the callback's
+    /// `CallbackGuard` alongside the builder; see `with_validator` for how
+    /// to use it.
+    pub fn with_cancellable_operation(&self, operation: impl Fn(Vec<Value>) -> Value + Send + Sync + 'static) -> Result<(IResource, CallbackGuard), Box<dyn std::error::Error>> {
         let mut args: HashMap<String, Value> = HashMap::new();
         args.insert("builder".to_string(), self.handle.to_json());
-        let callback_id = register_callback(operation);
-        args.insert("operation".to_string(), Value::String(callback_id));
+        let guard = self.client.register_callback_guarded(operation);
+        args.insert("operation".to_string(), Value::String(guard.id().to_string()));
         let result = self.client.invoke_capability("Aspire.Hosting.CodeGeneration.Rust.Tests/withCancellableOperation", args)?;
         let handle: Handle = serde_json::from_value(result)?;
-        Ok(IResource::new(handle, self.client.clone()))
+        Ok((IResource::new(handle, self.client.clone()), guard))
     }
 
     /// Configures vault using direct interface target
@@ -6009,14 +8570,88 @@ pub fn register_all_wrappers() {
 // ============================================================================
 
 /// Establishes a connection to the AppHost server.
+///
+/// Prefers `REMOTE_APP_HOST_ENDPOINT` (a single `unix://`, `npipe://`, or
+/// `tcp://` URL — see `crate::transport::parse_endpoint`), then
+/// `REMOTE_APP_HOST_TCP_ADDR` (a direct TCP endpoint, e.g. an AppHost
+/// running on a remote dev box or container host) and
+/// `REMOTE_APP_HOST_SSH_HOST`/`_SSH_PORT`/`_SSH_USER`/`_SSH_KEY_FILE`/
+/// `_REMOTE_SOCKET_PATH`/`_LOCAL_PORT` (tunneling to a remote AppHost socket
+/// over SSH) over the original `REMOTE_APP_HOST_SOCKET_PATH`, so existing
+/// same-machine setups keep working unchanged. Use `AspireClient::connect_with`
+/// directly instead of this env-var sniffing when the transport is already
+/// known (e.g. parsed from the app's own config).
 pub fn connect() -> Result<Arc<AspireClient>, Box<dyn std::error::Error>> {
+    if let Ok(endpoint) = std::env::var("REMOTE_APP_HOST_ENDPOINT") {
+        let config = crate::transport::parse_endpoint(&endpoint)?;
+        return AspireClient::connect_with(config);
+    }
+    if let Ok(addr) = std::env::var("REMOTE_APP_HOST_TCP_ADDR") {
+        let client = AspireClient::tcp(&addr);
+        client.connect()?;
+        return Ok(client);
+    }
+    if let Ok(host) = std::env::var("REMOTE_APP_HOST_SSH_HOST") {
+        let ssh_port = std::env::var("REMOTE_APP_HOST_SSH_PORT").ok().and_then(|v| v.parse().ok()).unwrap_or(22);
+        let user = std::env::var("REMOTE_APP_HOST_SSH_USER").unwrap_or_else(|_| "root".to_string());
+        let remote_socket_path = std::env::var("REMOTE_APP_HOST_REMOTE_SOCKET_PATH")
+            .map_err(|_| "REMOTE_APP_HOST_SSH_HOST set but REMOTE_APP_HOST_REMOTE_SOCKET_PATH is not")?;
+        let local_port = std::env::var("REMOTE_APP_HOST_LOCAL_PORT").ok().and_then(|v| v.parse().ok()).unwrap_or(19182);
+        let auth = if let Ok(key_file) = std::env::var("REMOTE_APP_HOST_SSH_KEY_FILE") {
+            crate::transport::SshAuth::KeyFile(key_file)
+        } else {
+            let password = std::env::var("REMOTE_APP_HOST_SSH_PASSWORD")
+                .map_err(|_| "REMOTE_APP_HOST_SSH_HOST set but neither REMOTE_APP_HOST_SSH_KEY_FILE nor REMOTE_APP_HOST_SSH_PASSWORD is")?;
+            crate::transport::SshAuth::Password(password)
+        };
+        return AspireClient::connect_with(crate::transport::TransportConfig::Ssh(crate::transport::SshConfig {
+            host,
+            ssh_port,
+            user,
+            auth,
+            remote_socket_path,
+            local_port,
+        }));
+    }
     let socket_path = std::env::var("REMOTE_APP_HOST_SOCKET_PATH")
         .map_err(|_| "REMOTE_APP_HOST_SOCKET_PATH environment variable not set. Run this application using `aspire run`")?;
-    let client = Arc::new(AspireClient::new(&socket_path));
+    let client = AspireClient::new(&socket_path);
     client.connect()?;
     Ok(client)
 }
 
+/// Async variant of `connect`, gated behind the `tokio` feature; see
+/// `AspireClient::connect_async`. Only recognizes `REMOTE_APP_HOST_ENDPOINT`'s
+/// `unix://`/`npipe://`/`tcp://` schemes plus the original
+/// `REMOTE_APP_HOST_SOCKET_PATH`, not the SSH tunnel variables, since
+/// spawning the `ssh` child process and waiting for the tunnel is itself a
+/// blocking operation `connect_with` isn't set up to run off the calling
+/// task the way `AspireClient::connect_async` runs the handshake.
+#[cfg(feature = "tokio")]
+pub async fn connect_async() -> Result<Arc<AspireClient>, AspireError> {
+    if let Ok(endpoint) = std::env::var("REMOTE_APP_HOST_ENDPOINT") {
+        let config = crate::transport::parse_endpoint(&endpoint)
+            .map_err(|e| AspireError::Transport(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)))?;
+        let client = match config {
+            crate::transport::TransportConfig::Socket(path) => AspireClient::new(&path),
+            crate::transport::TransportConfig::Tcp { host, port } => AspireClient::tcp(&format!("{}:{}", host, port)),
+            _ => {
+                return Err(AspireError::Transport(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "REMOTE_APP_HOST_ENDPOINT scheme is not supported by connect_async",
+                )))
+            }
+        };
+        client.connect_async().await?;
+        return Ok(client);
+    }
+    let socket_path = std::env::var("REMOTE_APP_HOST_SOCKET_PATH")
+        .map_err(|_| AspireError::Transport(std::io::Error::new(std::io::ErrorKind::NotFound, "REMOTE_APP_HOST_SOCKET_PATH environment variable not set. Run this application using `aspire run`")))?;
+    let client = AspireClient::new(&socket_path);
+    client.connect_async().await?;
+    Ok(client)
+}
+
 /// Creates a new distributed application builder.
 pub fn create_builder(options: Option<CreateBuilderOptions>) -> Result<IDistributedApplicationBuilder, Box<dyn std::error::Error>> {
     let client = connect()?;
@@ -6042,3 +8677,29 @@ pub fn create_builder(options: Option<CreateBuilderOptions>) -> Result<IDistribu
     Ok(IDistributedApplicationBuilder::new(handle, client))
 }
 
+/// Async variant of `create_builder`, gated behind the `tokio` feature.
+#[cfg(feature = "tokio")]
+pub async fn create_builder_async(options: Option<CreateBuilderOptions>) -> Result<IDistributedApplicationBuilder, AspireError> {
+    let client = connect_async().await?;
+    let mut resolved_options: HashMap<String, Value> = HashMap::new();
+    if let Some(opts) = options {
+        for (k, v) in opts.to_map() {
+            resolved_options.insert(k, v);
+        }
+    }
+    if !resolved_options.contains_key("Args") {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        resolved_options.insert("Args".to_string(), serde_json::to_value(args).unwrap_or(Value::Null));
+    }
+    if !resolved_options.contains_key("ProjectDirectory") {
+        if let Ok(pwd) = std::env::current_dir() {
+            resolved_options.insert("ProjectDirectory".to_string(), Value::String(pwd.to_string_lossy().to_string()));
+        }
+    }
+    let mut args: HashMap<String, Value> = HashMap::new();
+    args.insert("options".to_string(), serde_json::to_value(resolved_options).unwrap_or(Value::Null));
+    let result = client.invoke_capability_async("Aspire.Hosting/createBuilderWithOptions", args, None, None).await?;
+    let handle: Handle = serde_json::from_value(result)?;
+    Ok(IDistributedApplicationBuilder::new(handle, client))
+}
+